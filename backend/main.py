@@ -792,7 +792,9 @@ async def get_server_info():
             "port": current_server_config["port"],
             "started_at": current_server_config["started_at"],
             "status": "running",
-            "boot_token": os.environ.get("SACV_BOOT_TOKEN", "")
+            "boot_token": os.environ.get("SACV_BOOT_TOKEN", ""),
+            "install_dir": os.environ.get("SACV_INSTALL_DIR", ""),
+            "running_tasks": sum(1 for t in tasks_status.values() if t.status in ("pending", "processing"))
         },
         "timestamp": datetime.now().isoformat()
     }
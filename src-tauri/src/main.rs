@@ -6,12 +6,15 @@
     windows_subsystem = "windows"
 )]
 
+#[cfg(all(unix, not(target_os = "macos")))]
+mod utc_time;
+
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader};
 use std::net::TcpListener;
 use std::path::PathBuf;
 use std::process::{Child, Command, Stdio};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
@@ -26,7 +29,7 @@ use std::process::Stdio as _;
 use tauri::image::Image;
 use tauri::menu::{Menu, MenuItem};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 #[cfg(target_os = "windows")]
 use zip::ZipArchive;
 
@@ -36,6 +39,25 @@ use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+// 应用（Rust 桥接层）日志：同时输出到控制台与 super_auto_cut_app.log，打包版没有控制台时也能留存
+macro_rules! app_log {
+    (error, $($arg:tt)*) => {{
+        let msg = format!($($arg)*);
+        eprintln!("{}", msg);
+        write_app_log("ERROR", &msg);
+    }};
+    (warn, $($arg:tt)*) => {{
+        let msg = format!($($arg)*);
+        eprintln!("{}", msg);
+        write_app_log("WARN", &msg);
+    }};
+    (info, $($arg:tt)*) => {{
+        let msg = format!($($arg)*);
+        println!("{}", msg);
+        write_app_log("INFO", &msg);
+    }};
+}
+
 fn apply_windows_no_window(cmd: Command) -> Command {
     #[cfg(target_os = "windows")]
     {
@@ -57,28 +79,458 @@ fn kill_all_backend_processes() {
     let _ = apply_windows_no_window(cmd).status();
 }
 
-// 应用状态结构
-struct AppState {
+// 后台长任务（FFmpeg 下载、后端解压）追踪：退出时发取消信号并等待其清理临时文件
+static BACKGROUND_TASKS: AtomicUsize = AtomicUsize::new(0);
+static BACKGROUND_CANCEL: AtomicBool = AtomicBool::new(false);
+
+struct BackgroundTaskGuard;
+
+impl BackgroundTaskGuard {
+    fn begin() -> Self {
+        BACKGROUND_TASKS.fetch_add(1, Ordering::SeqCst);
+        BackgroundTaskGuard
+    }
+}
+
+impl Drop for BackgroundTaskGuard {
+    fn drop(&mut self) {
+        BACKGROUND_TASKS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// 后台任务（下载/解压）进度，供 list_background_tasks 统一查询
+#[derive(Clone, Serialize)]
+struct ProgressState {
+    id: String,
+    // ffmpeg_download | backend_extract | backend_update
+    kind: String,
+    // running | done | failed
+    status: String,
+    current: u64,
+    total: Option<u64>,
+    message: Option<String>,
+    started_at_ms: u64,
+    #[serde(skip)]
+    finished_at: Option<std::time::Instant>,
+}
+
+type ProgressRegistry = Arc<Mutex<HashMap<String, ProgressState>>>;
+
+// 已结束任务在列表中保留的时长
+const PROGRESS_RETAIN_SECS: u64 = 60;
+static PROGRESS_TASK_SEQ: AtomicU64 = AtomicU64::new(0);
+
+fn prune_progress_tasks(tasks: &mut HashMap<String, ProgressState>) {
+    tasks.retain(|_, t| {
+        t.finished_at.map_or(true, |f| {
+            f.elapsed() < Duration::from_secs(PROGRESS_RETAIN_SECS)
+        })
+    });
+}
+
+// 注册到进度表的后台任务；离开作用域时仍为 running 的标记为 failed（提前返回或中止）
+struct ProgressTask {
+    registry: ProgressRegistry,
+    id: String,
+}
+
+impl ProgressTask {
+    fn begin(registry: &ProgressRegistry, kind: &str) -> Self {
+        let seq = PROGRESS_TASK_SEQ.fetch_add(1, Ordering::SeqCst);
+        let id = format!("{}-{}", kind, seq);
+        let started_at_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let mut tasks = registry.lock().unwrap();
+        prune_progress_tasks(&mut tasks);
+        tasks.insert(
+            id.clone(),
+            ProgressState {
+                id: id.clone(),
+                kind: kind.to_string(),
+                status: "running".to_string(),
+                current: 0,
+                total: None,
+                message: None,
+                started_at_ms,
+                finished_at: None,
+            },
+        );
+        ProgressTask {
+            registry: registry.clone(),
+            id,
+        }
+    }
+
+    fn update(&self, current: u64, total: Option<u64>) {
+        if let Some(t) = self.registry.lock().unwrap().get_mut(&self.id) {
+            t.current = current;
+            t.total = total;
+        }
+    }
+
+    fn set_message(&self, message: &str) {
+        if let Some(t) = self.registry.lock().unwrap().get_mut(&self.id) {
+            t.message = Some(message.to_string());
+        }
+    }
+
+    fn finish<T>(&self, result: &Result<T, String>) {
+        if let Some(t) = self.registry.lock().unwrap().get_mut(&self.id) {
+            match result {
+                Ok(_) => {
+                    t.status = "done".to_string();
+                    if let Some(total) = t.total {
+                        t.current = total;
+                    }
+                }
+                Err(e) => {
+                    t.status = "failed".to_string();
+                    t.message = Some(e.clone());
+                }
+            }
+            t.finished_at = Some(std::time::Instant::now());
+        }
+    }
+}
+
+impl Drop for ProgressTask {
+    fn drop(&mut self) {
+        if let Ok(mut tasks) = self.registry.lock() {
+            if let Some(t) = tasks.get_mut(&self.id) {
+                if t.status == "running" {
+                    t.status = "failed".to_string();
+                    t.message
+                        .get_or_insert_with(|| "任务未完成即中止".to_string());
+                    t.finished_at = Some(std::time::Instant::now());
+                }
+            }
+        }
+    }
+}
+
+// 半成品文件/目录清理：离开作用域时删除仍存在的 .partial 路径（成功时已被重命名，不受影响）
+struct PartialCleanup(Vec<PathBuf>);
+
+impl Drop for PartialCleanup {
+    fn drop(&mut self) {
+        for p in &self.0 {
+            if p.is_dir() {
+                let _ = std::fs::remove_dir_all(p);
+            } else if p.exists() {
+                let _ = std::fs::remove_file(p);
+            }
+        }
+    }
+}
+
+fn background_cancelled() -> bool {
+    BACKGROUND_CANCEL.load(Ordering::SeqCst)
+}
+
+// 列出所有名为 superAutoCutVideoBackend 的进程 PID（不含自身）
+fn list_backend_pids() -> Vec<u32> {
+    let self_pid = std::process::id();
+    #[cfg(target_os = "windows")]
+    let output = {
+        let mut cmd = Command::new("tasklist");
+        cmd.args([
+            "/FI",
+            "IMAGENAME eq superAutoCutVideoBackend.exe",
+            "/FO",
+            "CSV",
+            "/NH",
+        ]);
+        apply_windows_no_window(cmd).output()
+    };
+    #[cfg(not(target_os = "windows"))]
+    let output = Command::new("pgrep")
+        .args(["-f", "(^|/)superAutoCutVideoBackend( |$)"])
+        .output();
+    let stdout = match output {
+        Ok(o) => String::from_utf8_lossy(&o.stdout).to_string(),
+        Err(_) => return Vec::new(),
+    };
+    stdout
+        .lines()
+        .filter_map(|line| {
+            if cfg!(target_os = "windows") {
+                // "superAutoCutVideoBackend.exe","1234","Console","1","50,000 K"
+                line.split(',')
+                    .nth(1)
+                    .map(|f| f.trim().trim_matches('"').to_string())
+            } else {
+                Some(line.trim().to_string())
+            }
+        })
+        .filter_map(|s| s.parse::<u32>().ok())
+        .filter(|pid| *pid != self_pid)
+        .collect()
+}
+
+// 结束进程及其子进程：Windows 用 taskkill /T；Unix 下后端以新 session 启动（pgid 即其 pid），
+// 对整个进程组发 SIGKILL，非组长进程（如外部启动的后端）退回只结束该 pid
+fn kill_process_tree(pid: u32) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new("taskkill");
+        cmd.args(["/F", "/T", "/PID", &pid.to_string()]);
+        apply_windows_no_window(cmd)
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+    #[cfg(unix)]
+    {
+        const SIGKILL: i32 = 9;
+        let pid = pid as i32;
+        unsafe { kill(-pid, SIGKILL) == 0 || kill(pid, SIGKILL) == 0 }
+    }
+}
+
+// 后端生命周期状态（权威就绪状态），变化时发射 backend-state-changed
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BackendLifecycle {
+    Stopped,
+    Starting,
+    Ready,
+    // 启动失败（提前退出/就绪超时）或运行中意外退出
+    Crashed,
+    // 崩溃后重启或空闲挂起后唤醒中
+    Reconnecting,
+}
+
+// 单个后端实例的运行状态；按 profile 区分，各实例独立端口、token 与进程
+#[derive(Clone)]
+struct BackendInstance {
+    profile: String,
     backend_process: Arc<Mutex<Option<Child>>>,
     backend_port: Arc<Mutex<u16>>,
     backend_starting: Arc<AtomicBool>,
+    backend_starting_since: Arc<Mutex<Option<std::time::Instant>>>,
+    // 启动流程当前所处阶段（discovery/prepare_resources/spawn/wait_ready），总超时时用于报告卡在哪一步
+    start_stage: Arc<Mutex<&'static str>>,
     backend_boot_token: Arc<Mutex<Option<String>>>,
-    app_is_quitting: Arc<AtomicBool>,
+    backend_boot_trace: Arc<Mutex<HashMap<String, String>>>,
+    backend_source: Arc<Mutex<String>>,
+    backend_uds_path: Arc<Mutex<Option<PathBuf>>>,
+    // Windows Job Object 句柄（整组结束后端及其子进程）
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    backend_job: Arc<Mutex<Option<usize>>>,
+    port_override: Arc<Mutex<Option<u16>>>,
+    // 空闲挂起方式（sleep=后端卸载模型，stop=已停止后端）
+    backend_suspended: Arc<Mutex<Option<String>>>,
+    // 最近的 stderr 行（崩溃快照用）
+    stderr_tail: Arc<Mutex<std::collections::VecDeque<String>>>,
+    lifecycle: Arc<Mutex<BackendLifecycle>>,
+    // 意外退出后的自动重连是否进行中，以及用户是否要求放弃重连
+    reconnect_active: Arc<AtomicBool>,
+    reconnect_cancel: Arc<AtomicBool>,
+    // 本实例后端 stdout/stderr 日志读取线程句柄，停止/重启时回收
+    log_threads: Arc<Mutex<Vec<thread::JoinHandle<()>>>>,
+    // 就绪后缓存的 /api/server/info data
+    server_info: Arc<Mutex<Option<serde_json::Value>>>,
+    // 上次启动时注入后端的环境变量快照（敏感值已脱敏）
+    launch_env: Arc<Mutex<std::collections::BTreeMap<String, String>>>,
 }
 
-impl Default for AppState {
-    fn default() -> Self {
+impl BackendInstance {
+    fn new(profile: &str) -> Self {
         Self {
+            profile: profile.to_string(),
             backend_process: Arc::new(Mutex::new(None)),
             backend_port: Arc::new(Mutex::new(0)),
             backend_starting: Arc::new(AtomicBool::new(false)),
+            backend_starting_since: Arc::new(Mutex::new(None)),
+            start_stage: Arc::new(Mutex::new("idle")),
             backend_boot_token: Arc::new(Mutex::new(None)),
+            backend_boot_trace: Arc::new(Mutex::new(HashMap::new())),
+            backend_source: Arc::new(Mutex::new("none".to_string())),
+            backend_uds_path: Arc::new(Mutex::new(None)),
+            backend_job: Arc::new(Mutex::new(None)),
+            port_override: Arc::new(Mutex::new(None)),
+            backend_suspended: Arc::new(Mutex::new(None)),
+            stderr_tail: Arc::new(Mutex::new(std::collections::VecDeque::new())),
+            lifecycle: Arc::new(Mutex::new(BackendLifecycle::Stopped)),
+            reconnect_active: Arc::new(AtomicBool::new(false)),
+            reconnect_cancel: Arc::new(AtomicBool::new(false)),
+            log_threads: Arc::new(Mutex::new(Vec::new())),
+            server_info: Arc::new(Mutex::new(None)),
+            launch_env: Arc::new(Mutex::new(std::collections::BTreeMap::new())),
+        }
+    }
+
+    // 回收日志读取线程：进程结束后管道 EOF 会让线程自行退出，最多等待 timeout；
+    // 仍未退出的（如孙进程还持有管道）放回列表留待下次回收，不阻塞停止流程
+    fn reap_log_threads(&self, timeout: Duration) {
+        let mut pending = std::mem::take(&mut *self.log_threads.lock().unwrap());
+        let deadline = std::time::Instant::now() + timeout;
+        while pending.iter().any(|h| !h.is_finished()) && std::time::Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+        let (finished, alive): (Vec<_>, Vec<_>) =
+            pending.into_iter().partition(|h| h.is_finished());
+        for handle in finished {
+            let _ = handle.join();
+        }
+        if !alive.is_empty() {
+            app_log!(
+                warn,
+                "[backend] {} 个日志读取线程尚未退出 (profile={})",
+                alive.len(),
+                self.profile
+            );
+            self.log_threads.lock().unwrap().extend(alive);
+        }
+    }
+
+    fn lifecycle(&self) -> BackendLifecycle {
+        *self.lifecycle.lock().unwrap()
+    }
+
+    // 更新生命周期状态，发生变化时发射 backend-state-changed {profile, state, previous}
+    fn set_lifecycle(&self, app_handle: &AppHandle, next: BackendLifecycle) {
+        let previous = std::mem::replace(&mut *self.lifecycle.lock().unwrap(), next);
+        if previous != next {
+            let _ = app_handle.emit(
+                "backend-state-changed",
+                serde_json::json!({ "profile": self.profile, "state": next, "previous": previous }),
+            );
+        }
+    }
+
+    fn is_default(&self) -> bool {
+        self.profile == DEFAULT_BACKEND_PROFILE
+    }
+}
+
+// 未指定 profile 时使用的默认后端实例（旧前端不传 profile 时行为不变）
+const DEFAULT_BACKEND_PROFILE: &str = "default";
+
+// 应用状态结构
+struct AppState {
+    backends: Arc<Mutex<HashMap<String, BackendInstance>>>,
+    output_root: Arc<Mutex<Option<String>>>,
+    // 最近一次前端活动时间（空闲挂起计时用）
+    last_activity: Arc<Mutex<std::time::Instant>>,
+    window_state_gen: Arc<AtomicU64>,
+    app_is_quitting: Arc<AtomicBool>,
+    // 退出已确认（无运行中任务或用户已在前端确认），CloseRequested 不再拦截
+    quit_confirmed: Arc<AtomicBool>,
+    // 用户通过对话框选择过的目录（规范化路径），read_text_file/write_text_file 仅允许访问其中的文件
+    authorized_dirs: Arc<Mutex<Vec<PathBuf>>>,
+    progress_tasks: ProgressRegistry,
+    // 命令行 --batch 传入、尚未被前端取走的文件清单
+    pending_batch_files: Arc<Mutex<Option<BatchFiles>>>,
+    // 启动时发现的其它安装目录下的后端，等待用户选择复用或新建
+    pending_install_conflict: Arc<Mutex<Option<InstallConflict>>>,
+    // 用户选择不复用的后端端口，默认实例发现时跳过
+    ignored_backend_ports: Arc<Mutex<Vec<u16>>>,
+    // backend_request 进行中的代理请求：前端请求 id → 中止句柄，供 cancel_backend_request 取消
+    backend_requests: Arc<Mutex<HashMap<String, tokio::task::AbortHandle>>>,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        let mut backends = HashMap::new();
+        backends.insert(
+            DEFAULT_BACKEND_PROFILE.to_string(),
+            BackendInstance::new(DEFAULT_BACKEND_PROFILE),
+        );
+        Self {
+            backends: Arc::new(Mutex::new(backends)),
+            output_root: Arc::new(Mutex::new(
+                std::env::var("SACV_OUTPUT_ROOT")
+                    .ok()
+                    .filter(|s| !s.trim().is_empty()),
+            )),
+            last_activity: Arc::new(Mutex::new(std::time::Instant::now())),
+            window_state_gen: Arc::new(AtomicU64::new(0)),
             app_is_quitting: Arc::new(AtomicBool::new(false)),
+            quit_confirmed: Arc::new(AtomicBool::new(false)),
+            authorized_dirs: Arc::new(Mutex::new(Vec::new())),
+            progress_tasks: Arc::new(Mutex::new(HashMap::new())),
+            pending_batch_files: Arc::new(Mutex::new(None)),
+            pending_install_conflict: Arc::new(Mutex::new(None)),
+            ignored_backend_ports: Arc::new(Mutex::new(Vec::new())),
+            backend_requests: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+// profile 参数归一化：None 或空串为默认实例
+fn profile_name(profile: Option<&str>) -> &str {
+    profile
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .unwrap_or(DEFAULT_BACKEND_PROFILE)
+}
+
+impl AppState {
+    // 获取（不存在则创建）指定 profile 的后端实例；仅启动与启动前配置使用，查询类命令用 existing_backend
+    fn backend(&self, profile: Option<&str>) -> BackendInstance {
+        let name = profile_name(profile);
+        self.backends
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert_with(|| BackendInstance::new(name))
+            .clone()
+    }
+
+    // 只查找不创建：未启动过的 profile 返回错误，避免查询命令凭任意名字生成空实例
+    fn existing_backend(&self, profile: Option<&str>) -> Result<BackendInstance, String> {
+        let name = profile_name(profile);
+        self.backends
+            .lock()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("后端 profile 不存在: {}", name))
+    }
+
+    // 记录用户授权的目录（规范化后去重）
+    fn authorize_dir(&self, dir: &std::path::Path) {
+        if let Ok(dir) = std::fs::canonicalize(dir) {
+            let mut dirs = self.authorized_dirs.lock().unwrap();
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+
+    fn all_backends(&self) -> Vec<BackendInstance> {
+        self.backends.lock().unwrap().values().cloned().collect()
+    }
+
+    // 除指定 profile 外的其它后端实例
+    fn other_backends(&self, profile: &str) -> Vec<BackendInstance> {
+        self.all_backends()
+            .into_iter()
+            .filter(|b| b.profile != profile)
+            .collect()
+    }
+}
+
+// 校验 profile 名称：仅允许字母、数字、- 与 _
+fn validate_profile(profile: &Option<String>) -> Result<(), String> {
+    match profile {
+        Some(p)
+            if !p
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+        {
+            Err(format!("后端 profile 名称不合法: {}", p))
         }
+        _ => Ok(()),
     }
 }
 
 const BACKEND_IDENTIFIER: &str = "super-auto-cut-video-backend";
+// 启动标志超过该秒数仍未释放即视为陈旧
+const BACKEND_STARTING_STALE_SECS: u64 = 120;
 
 // 后端状态响应
 #[derive(Serialize, Deserialize, Debug)]
@@ -87,6 +539,12 @@ struct BackendStatus {
     port: u16,
     pid: Option<u32>,
     boot_token: Option<String>,
+    // 来源：spawned（本次启动）/discovered（发现已有后端）/log-parsed（从日志解析端口）/reused（复用运行中的进程或启动流程）/none
+    source: String,
+    // 本次 spawn 到就绪的耗时（毫秒），仅 source 为 spawned 时有值
+    ready_ms: Option<u64>,
+    // 就绪后拉取并缓存的 /api/server/info data（版本、能力标志等）
+    server_info: Option<serde_json::Value>,
 }
 
 // 文件选择结果
@@ -94,28 +552,297 @@ struct BackendStatus {
 struct FileSelection {
     path: Option<String>,
     cancelled: bool,
+    writable: Option<bool>,
+    real_path: Option<String>,
+}
+
+// Unix 可选 UDS 模式（SACV_BACKEND_UDS=1）：后端监听仅当前用户可访问目录下的 socket 文件，避免多用户机器上端口被探测
+#[cfg(unix)]
+fn prepare_backend_uds_path(app_handle: &AppHandle, profile: &str) -> Option<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+    if std::env::var("SACV_BACKEND_UDS").ok().as_deref() != Some("1") {
+        return None;
+    }
+    let dir = app_handle.path().app_cache_dir().ok()?.join("run");
+    std::fs::create_dir_all(&dir).ok()?;
+    std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700)).ok()?;
+    let sock = if profile == DEFAULT_BACKEND_PROFILE {
+        dir.join("backend.sock")
+    } else {
+        dir.join(format!("backend-{}.sock", profile))
+    };
+    // 清理上次残留的 socket 文件，否则后端 bind 会失败
+    let _ = std::fs::remove_file(&sock);
+    Some(sock)
+}
+
+#[cfg(not(unix))]
+fn prepare_backend_uds_path(_app_handle: &AppHandle, _profile: &str) -> Option<PathBuf> {
+    None
+}
+
+// 与后端通信的 HTTP 客户端：UDS 模式下所有请求走 socket 文件（URL 中的主机端口仅作占位）
+fn backend_client_builder(backend: &BackendInstance) -> reqwest::ClientBuilder {
+    let builder = reqwest::Client::builder();
+    #[cfg(unix)]
+    if let Some(sock) = backend.backend_uds_path.lock().unwrap().clone() {
+        return builder.unix_socket(sock);
+    }
+    #[cfg(not(unix))]
+    let _ = backend;
+    builder
+}
+
+// 后端探测失败分类：区分"端口没人监听"（ConnRefused/Timeout）与"后端在但报错"（BadStatus/BadPayload/NotReady）
+#[derive(Debug, Clone, PartialEq)]
+enum BackendProbeError {
+    ConnRefused,
+    Timeout,
+    BadStatus(u16),
+    BadPayload,
+    NotReady(String),
+}
+
+impl std::fmt::Display for BackendProbeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendProbeError::ConnRefused => write!(f, "连接拒绝"),
+            BackendProbeError::Timeout => write!(f, "超时"),
+            BackendProbeError::BadStatus(code) => write!(f, "非 2xx 响应 HTTP {}", code),
+            BackendProbeError::BadPayload => write!(f, "响应内容不符合预期"),
+            BackendProbeError::NotReady(stage) => {
+                write!(f, "端口已监听但应用层未就绪（stage={}）", stage)
+            }
+        }
+    }
+}
+
+impl From<reqwest::Error> for BackendProbeError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            BackendProbeError::Timeout
+        } else if e.is_decode() || e.is_body() {
+            BackendProbeError::BadPayload
+        } else {
+            BackendProbeError::ConnRefused
+        }
+    }
+}
+
+// 后端启动失败原因码：序列化为 {"code": "<变体名>", "message": ..., ...}，前端据 code 显示本地化文案与对应操作
+#[derive(Debug, Serialize)]
+#[serde(tag = "code")]
+enum BackendError {
+    InvalidArgument {
+        message: String,
+    },
+    AlreadyStarting {
+        message: String,
+    },
+    ExecutableNotFound {
+        message: String,
+    },
+    ExtractFailed {
+        message: String,
+    },
+    PortUnavailable {
+        message: String,
+    },
+    SpawnFailed {
+        message: String,
+    },
+    ReadyTimeout {
+        message: String,
+    },
+    EarlyExit {
+        exit_code: Option<i32>,
+        message: String,
+    },
+    // 整个启动流程超过总超时，stage 为超时时所处阶段
+    StartTimeout {
+        stage: String,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            BackendError::InvalidArgument { message }
+            | BackendError::AlreadyStarting { message }
+            | BackendError::ExecutableNotFound { message }
+            | BackendError::ExtractFailed { message }
+            | BackendError::PortUnavailable { message }
+            | BackendError::SpawnFailed { message }
+            | BackendError::ReadyTimeout { message }
+            | BackendError::EarlyExit { message, .. }
+            | BackendError::StartTimeout { message, .. } => message,
+        };
+        write!(f, "{}", message)
+    }
+}
+
+// 参数校验、停止旧进程等返回字符串错误的辅助函数可直接用 ? 转换
+impl From<String> for BackendError {
+    fn from(message: String) -> Self {
+        BackendError::InvalidArgument { message }
+    }
 }
 
-async fn wait_for_backend_ready(host: &str, port: u16, total_wait_secs: u64) -> bool {
-    let url = format!("http://{}:{}/api/hello", host, port);
-    let client = match reqwest::Client::builder()
+// 识别 Python 后端启动即失败的典型 stderr 行（缺依赖、导入失败、语法错误）；
+// 仅作线索，被捕获后降级处理的导入失败同样会打印，需结合进程是否已退出判断
+fn is_python_startup_error(line: &str) -> bool {
+    ["ModuleNotFoundError", "ImportError", "SyntaxError"]
+        .iter()
+        .any(|k| line.contains(k))
+}
+
+// 后端健康检查候选端点，按序尝试，任一返回 200 即视为健康
+const DEFAULT_HEALTH_ENDPOINTS: &[&str] = &["/api/hello", "/healthz"];
+
+// 健康检查端点：SACV_HEALTH_ENDPOINTS（逗号分隔，须以 / 开头）优先，否则使用默认候选
+fn backend_health_endpoints() -> Vec<String> {
+    let configured: Vec<String> = std::env::var("SACV_HEALTH_ENDPOINTS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|e| e.trim())
+        .filter(|e| e.starts_with('/'))
+        .map(|e| e.to_string())
+        .collect();
+    if configured.is_empty() {
+        DEFAULT_HEALTH_ENDPOINTS
+            .iter()
+            .map(|e| e.to_string())
+            .collect()
+    } else {
+        configured
+    }
+}
+
+// 依次请求健康检查端点，任一 200 即返回 Ok；全部失败时返回最后一个错误
+async fn probe_health_endpoints(
+    client: &reqwest::Client,
+    host: &str,
+    port: u16,
+    endpoints: &[String],
+) -> Result<(), BackendProbeError> {
+    let mut last_error = BackendProbeError::ConnRefused;
+    for endpoint in endpoints {
+        let url = format!("http://{}:{}{}", host, port, endpoint);
+        match client.get(&url).send().await {
+            Ok(resp) if resp.status().is_success() => return Ok(()),
+            Ok(resp) => last_error = BackendProbeError::BadStatus(resp.status().as_u16()),
+            // 连不上时其余端点也不可能成功
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Err(last_error)
+}
+
+// 检查指定 pid 的后端子进程是否已退出：已退出返回 Some(退出码)；已被退出监控回收或被替换时退出码未知
+fn backend_child_exit(backend: &BackendInstance, pid: u32) -> Option<Option<i32>> {
+    let mut guard = backend.backend_process.lock().unwrap();
+    match guard.as_mut() {
+        Some(c) if c.id() == pid => c.try_wait().ok().flatten().map(|s| s.code()),
+        _ => Some(None),
+    }
+}
+
+// 等待后端就绪：优先探测 /api/server/ready 返回的 {ready, stage}，阶段变化时发射 backend-ready-progress 事件，
+// 只有 ready 为 true 才算成功；该端点返回非 2xx 或响应结构不符时回退到健康检查端点（默认 /api/hello、/healthz）返回 200 即视为就绪
+async fn wait_for_backend_ready(
+    app_handle: &AppHandle,
+    backend: &BackendInstance,
+    host: &str,
+    port: u16,
+    total_wait_secs: u64,
+    pid: u32,
+    startup_error: &Mutex<Option<String>>,
+) -> Result<(), BackendProbeError> {
+    // 等待期间阻止 macOS App Nap 节流轮询定时器
+    let _app_nap = AppNapGuard::begin("等待后端服务就绪");
+    let health_endpoints = backend_health_endpoints();
+    let ready_url = format!("http://{}:{}/api/server/ready", host, port);
+    let mut ready_endpoint_supported = true;
+    let mut last_stage: Option<String> = None;
+    let client = match backend_client_builder(backend)
         .timeout(Duration::from_millis(3000))
         .build()
     {
         Ok(c) => c,
-        Err(_) => return false,
+        Err(_) => return Err(BackendProbeError::ConnRefused),
     };
 
     let attempts = total_wait_secs * 4; // 250ms * 4 per second
+                                        // 每 5 秒写一条心跳日志，记录最近一次探测错误，区分"后端还没起"与"起了但接口报错"
+    let started = std::time::Instant::now();
+    let mut last_heartbeat_secs = 0u64;
+    let mut last_error = BackendProbeError::ConnRefused;
     for _ in 0..attempts {
-        match client.get(&url).send().await {
-            Ok(resp) if resp.status().is_success() => return true,
-            _ => {
-                tokio::time::sleep(Duration::from_millis(250)).await;
+        // stderr 出现启动错误且进程确已退出时无需继续等待；进程仍在运行则视为非致命，继续等待
+        if startup_error.lock().unwrap().is_some() && backend_child_exit(backend, pid).is_some() {
+            return Err(last_error);
+        }
+        if ready_endpoint_supported {
+            match client.get(&ready_url).send().await {
+                // 非 2xx（404/405 未实现、401 鉴权不符、5xx 路由异常等）不会自行恢复，改用健康检查端点，避免一直轮询到超时
+                Ok(resp) if !resp.status().is_success() => {
+                    last_error = BackendProbeError::BadStatus(resp.status().as_u16());
+                    ready_endpoint_supported = false;
+                }
+                Ok(resp) => {
+                    let body = resp.json::<serde_json::Value>().await.ok();
+                    match body
+                        .as_ref()
+                        .and_then(|v| v.get("ready"))
+                        .and_then(|r| r.as_bool())
+                    {
+                        Some(ready) => {
+                            let stage = body
+                                .as_ref()
+                                .and_then(|v| v.get("stage"))
+                                .and_then(|s| s.as_str())
+                                .unwrap_or("")
+                                .to_string();
+                            if ready || last_stage.as_deref() != Some(stage.as_str()) {
+                                let _ = app_handle.emit(
+                                    "backend-ready-progress",
+                                    serde_json::json!({ "profile": backend.profile, "port": port, "ready": ready, "stage": stage }),
+                                );
+                            }
+                            if ready {
+                                return Ok(());
+                            }
+                            last_error = BackendProbeError::NotReady(stage.clone());
+                            last_stage = Some(stage);
+                        }
+                        // 响应不是预期结构，视为不支持该端点
+                        None => ready_endpoint_supported = false,
+                    }
+                }
+                Err(e) => last_error = e.into(),
             }
         }
+        if !ready_endpoint_supported {
+            match probe_health_endpoints(&client, host, port, &health_endpoints).await {
+                Ok(()) => return Ok(()),
+                Err(e) => last_error = e,
+            }
+        }
+        let waited = started.elapsed().as_secs();
+        if waited >= last_heartbeat_secs + 5 {
+            last_heartbeat_secs = waited;
+            append_log_line(
+                backend_log_path(),
+                &format!(
+                    "[meta] 仍在等待就绪，已等待 {}s，最近一次探测错误：{}",
+                    waited, last_error
+                ),
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
     }
-    false
+    Err(last_error)
 }
 
 fn generate_boot_token() -> String {
@@ -128,80 +855,68 @@ fn generate_boot_token() -> String {
     out
 }
 
+// 发现扫描的 TCP 连接超时：与总超时分开，被防火墙静默丢包的端口能快速跳过
+const DISCOVERY_CONNECT_TIMEOUT_MS: u64 = 200;
+
 async fn discover_existing_backend(
     host: &str,
     require_token: bool,
 ) -> Option<(u16, Option<String>)> {
     let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_millis(DISCOVERY_CONNECT_TIMEOUT_MS))
         .timeout(Duration::from_millis(600))
         .build()
         .ok()?;
+    // 显式指定了 dev 端口时只探测该端口，避免连到同机其它项目的后端
+    if let Some(p) = configured_dev_port() {
+        return probe_server_info(&client, host, p, require_token)
+            .await
+            .ok();
+    }
     let ranges: &[(u16, u16)] = &[(8000, 8101), (18000, 18101)];
     for (start, end) in ranges {
         for p in *start..*end {
-            let url = format!("http://{}:{}/api/server/info", host, p);
-            let resp = match client.get(&url).send().await {
-                Ok(r) => r,
-                Err(_) => continue,
-            };
-            if !resp.status().is_success() {
-                continue;
-            }
-            let v: serde_json::Value = match resp.json().await {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
-            let data = match v.get("data") {
-                Some(d) => d,
-                None => continue,
-            };
-            let identifier = match data.get("identifier").and_then(|s| s.as_str()) {
-                Some(s) => s,
-                None => continue,
-            };
-            if identifier != BACKEND_IDENTIFIER {
-                continue;
+            if let Ok(found) = probe_server_info(&client, host, p, require_token).await {
+                return Some(found);
             }
-            let reported_port = data
-                .get("port")
-                .and_then(|n| n.as_u64())
-                .and_then(|n| u16::try_from(n).ok())
-                .unwrap_or(p);
-            let boot_token = data
-                .get("boot_token")
-                .and_then(|t| t.as_str())
-                .map(|t| t.to_string())
-                .filter(|t| !t.is_empty());
-            if require_token && boot_token.is_none() {
-                continue;
-            }
-            return Some((reported_port, boot_token));
         }
     }
     None
 }
 
-async fn check_backend_on_port(
+// 纯探测：请求 /api/server/info 并校验是本应用后端，返回其 data；不读写任何状态
+async fn fetch_server_info_data(
+    client: &reqwest::Client,
     host: &str,
     port: u16,
-    timeout_ms: u64,
-    require_token: bool,
-) -> Option<(u16, Option<String>)> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_millis(timeout_ms))
-        .build()
-        .ok()?;
+) -> Result<serde_json::Value, BackendProbeError> {
     let url = format!("http://{}:{}/api/server/info", host, port);
-    let resp = client.get(&url).send().await.ok()?;
+    let resp = client.get(&url).send().await?;
     if !resp.status().is_success() {
-        return None;
+        return Err(BackendProbeError::BadStatus(resp.status().as_u16()));
     }
-    let v: serde_json::Value = resp.json().await.ok()?;
-    let data = v.get("data")?;
-    let identifier = data.get("identifier").and_then(|s| s.as_str())?;
+    let mut v: serde_json::Value = resp.json().await?;
+    let data = v
+        .get_mut("data")
+        .map(serde_json::Value::take)
+        .ok_or(BackendProbeError::BadPayload)?;
+    let identifier = data
+        .get("identifier")
+        .and_then(|s| s.as_str())
+        .ok_or(BackendProbeError::BadPayload)?;
     if identifier != BACKEND_IDENTIFIER {
-        return None;
+        return Err(BackendProbeError::BadPayload);
     }
+    Ok(data)
+}
+
+async fn probe_server_info(
+    client: &reqwest::Client,
+    host: &str,
+    port: u16,
+    require_token: bool,
+) -> Result<(u16, Option<String>), BackendProbeError> {
+    let data = fetch_server_info_data(client, host, port).await?;
     let reported_port = data
         .get("port")
         .and_then(|n| n.as_u64())
@@ -213,22 +928,102 @@ async fn check_backend_on_port(
         .map(|t| t.to_string())
         .filter(|t| !t.is_empty());
     if require_token && boot_token.is_none() {
-        return None;
+        return Err(BackendProbeError::BadPayload);
+    }
+    Ok((reported_port, boot_token))
+}
+
+async fn check_backend_on_port(
+    host: &str,
+    port: u16,
+    timeout_ms: u64,
+    require_token: bool,
+) -> Result<(u16, Option<String>), BackendProbeError> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_millis(
+            timeout_ms.min(DISCOVERY_CONNECT_TIMEOUT_MS),
+        ))
+        .timeout(Duration::from_millis(timeout_ms))
+        .build()
+        .map_err(|_| BackendProbeError::ConnRefused)?;
+    let result = probe_server_info(&client, host, port, require_token).await;
+    if let Err(e) = &result {
+        append_log_line(
+            backend_log_path(),
+            &format!("[meta] probe_backend port={} error={:?}", port, e),
+        );
+    }
+    result
+}
+
+// 探测并记录：把发现的已运行后端写入实例状态并返回对应的 BackendStatus
+fn record_discovered_backend(
+    backend: &BackendInstance,
+    port: u16,
+    boot_token: Option<String>,
+    pid: Option<u32>,
+) -> BackendStatus {
+    if let Some(token) = &boot_token {
+        register_boot_token(token);
+    }
+    *backend.backend_port.lock().unwrap() = port;
+    *backend.backend_boot_token.lock().unwrap() = boot_token.clone();
+    *backend.backend_source.lock().unwrap() = "discovered".to_string();
+    BackendStatus {
+        running: true,
+        port,
+        pid,
+        boot_token,
+        source: "discovered".to_string(),
+        ready_ms: None,
+        server_info: None,
+    }
+}
+
+// Tauri命令：只读探测后端（不修改 AppState）。指定 port 时只探测该端口，否则按快速发现顺序查找；
+// 返回后端自报的 /api/server/info data（去掉 boot_token），未发现时返回 None
+#[tauri::command]
+async fn peek_backend(port: Option<u16>) -> Result<Option<serde_json::Value>, String> {
+    let host = "127.0.0.1";
+    let port = match port {
+        Some(p) => p,
+        None => match discover_existing_backend_quick(host, false).await {
+            Some((p, _)) => p,
+            None => return Ok(None),
+        },
+    };
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(1000))
+        .build()
+        .map_err(|e| format!("创建请求客户端失败: {}", e))?;
+    match fetch_server_info_data(&client, host, port).await {
+        Ok(mut data) => {
+            if let Some(obj) = data.as_object_mut() {
+                obj.remove("boot_token");
+            }
+            Ok(Some(data))
+        }
+        Err(_) => Ok(None),
     }
-    Some((reported_port, boot_token))
 }
 
 async fn discover_existing_backend_quick(
     host: &str,
     require_token: bool,
 ) -> Option<(u16, Option<String>)> {
+    // 固定范围全部占满时会使用随机端口，优先探测缓存下来的该端口
+    if let Some(p) = read_cached_backend_port() {
+        if let Ok(found) = check_backend_on_port(host, p, 200, require_token).await {
+            return Some(found);
+        }
+    }
     if let Some(p) = parse_backend_port_from_log() {
-        if let Some(found) = check_backend_on_port(host, p, 200, require_token).await {
+        if let Ok(found) = check_backend_on_port(host, p, 200, require_token).await {
             return Some(found);
         }
     }
     for p in [18000u16, 8000u16] {
-        if let Some(found) = check_backend_on_port(host, p, 200, require_token).await {
+        if let Ok(found) = check_backend_on_port(host, p, 200, require_token).await {
             return Some(found);
         }
     }
@@ -236,7 +1031,7 @@ async fn discover_existing_backend_quick(
 }
 
 fn parse_backend_port_from_log() -> Option<u16> {
-    let log_path = std::env::temp_dir().join("super_auto_cut_backend.log");
+    let log_path = backend_log_path();
     let content = std::fs::read_to_string(&log_path).ok()?;
     let needles = [
         "Uvicorn running on http://127.0.0.1:",
@@ -265,10 +1060,16 @@ fn parse_backend_port_from_log() -> Option<u16> {
     }
     if let Some(pos) = content.rfind("http://127.0.0.1:") {
         let line_start = content[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
-        let line_end = content[pos..].find('\n').map(|i| pos + i).unwrap_or(content.len());
+        let line_end = content[pos..]
+            .find('\n')
+            .map(|i| pos + i)
+            .unwrap_or(content.len());
         let line = &content[line_start..line_end];
         let lower = line.to_lowercase();
-        if lower.contains("running on") || lower.contains("listening on") || lower.contains("serving on") {
+        if lower.contains("running on")
+            || lower.contains("listening on")
+            || lower.contains("serving on")
+        {
             let start = pos + "http://127.0.0.1:".len();
             let bytes = content.as_bytes();
             let mut i = start;
@@ -290,96 +1091,421 @@ fn parse_backend_port_from_log() -> Option<u16> {
     None
 }
 
+// 离线放置的 FFmpeg 目录（SACV_FFMPEG_DIR）
+fn offline_ffmpeg_dir() -> Option<PathBuf> {
+    std::env::var("SACV_FFMPEG_DIR")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+        .map(PathBuf::from)
+        .filter(|p| p.is_dir())
+}
+
+// 下载选项：单次超时、重试次数与指数退避、进度回调、SHA-256 校验
+struct DownloadOptions<'a> {
+    timeout: Duration,
+    connect_timeout: Duration,
+    retries: u32,
+    backoff: Duration,
+    sha256: Option<String>,
+    proxy: Option<String>,
+    on_progress: Option<&'a (dyn Fn(u64, Option<u64>) + Send + Sync)>,
+}
+
+impl Default for DownloadOptions<'_> {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(600),
+            connect_timeout: Duration::from_secs(15),
+            retries: 3,
+            backoff: Duration::from_secs(2),
+            sha256: None,
+            proxy: None,
+            on_progress: None,
+        }
+    }
+}
+
+// 下载代理地址：环境变量 SACV_HTTP_PROXY 优先，否则取配置 http_proxy；均未设置时不显式指定代理
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn download_proxy_url(app_handle: &AppHandle) -> Option<String> {
+    std::env::var("SACV_HTTP_PROXY")
+        .ok()
+        .or_else(|| {
+            load_app_config(app_handle)
+                .get("http_proxy")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+        })
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+// 解析代理地址：省略 scheme 时按 http 处理；user:pass@ 形式的认证信息按最后一个 @ 拆分后以 Basic 认证传入，
+// 以兼容密码中包含 @、: 等未转义字符的写法。返回的第二项为隐去密码的地址，用于日志
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn build_download_proxy(raw: &str) -> Result<(reqwest::Proxy, String), String> {
+    let (scheme, rest) = match raw.split_once("://") {
+        Some((s, r)) => (s.to_lowercase(), r),
+        None => ("http".to_string(), raw),
+    };
+    if scheme != "http" && scheme != "https" {
+        return Err(format!("不支持的代理协议: {}（仅支持 http/https）", scheme));
+    }
+    let (auth, host) = match rest.rfind('@') {
+        Some(i) => (Some(&rest[..i]), &rest[i + 1..]),
+        None => (None, rest),
+    };
+    let host = host.trim_end_matches('/');
+    if host.is_empty() {
+        return Err(format!("代理地址无效: {}", raw));
+    }
+    let url = format!("{}://{}", scheme, host);
+    let mut proxy =
+        reqwest::Proxy::all(&url).map_err(|e| format!("代理地址无效 {}: {}", url, e))?;
+    let display = match auth {
+        Some(auth) => {
+            let (user, pass) = auth.split_once(':').unwrap_or((auth, ""));
+            proxy = proxy.basic_auth(user, pass);
+            format!("{}://{}:***@{}", scheme, user, host)
+        }
+        None => url,
+    };
+    Ok((proxy, display))
+}
+
+// 单次下载到 partial 文件；错误附带是否值得重试
+async fn download_once(
+    client: &reqwest::Client,
+    url: &str,
+    partial: &std::path::Path,
+    opts: &DownloadOptions<'_>,
+) -> Result<(), (String, bool)> {
+    use sha2::{Digest, Sha256};
+    use std::io::Write;
+    let mut resp = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| (format!("请求失败: {}", e), true))?;
+    let status = resp.status();
+    if !status.is_success() {
+        let retryable = status.is_server_error() || status.as_u16() == 429;
+        return Err((format!("返回状态异常: {}", status), retryable));
+    }
+    let total = resp.content_length();
+    let mut file = std::fs::File::create(partial)
+        .map_err(|e| (format!("创建文件失败 {:?}: {}", partial, e), false))?;
+    let mut hasher = Sha256::new();
+    let mut downloaded: u64 = 0;
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| (format!("读取下载内容失败: {}", e), true))?
+    {
+        if background_cancelled() {
+            return Err(("应用正在退出，已中止下载".to_string(), false));
+        }
+        file.write_all(&chunk)
+            .map_err(|e| (format!("写入文件失败 {:?}: {}", partial, e), false))?;
+        hasher.update(&chunk);
+        downloaded += chunk.len() as u64;
+        if let Some(cb) = opts.on_progress {
+            cb(downloaded, total);
+        }
+    }
+    if let Some(expected) = &opts.sha256 {
+        let mut actual = String::with_capacity(64);
+        for b in hasher.finalize() {
+            actual.push_str(&format!("{:02x}", b));
+        }
+        if !actual.eq_ignore_ascii_case(expected.trim()) {
+            return Err((
+                format!(
+                    "文件校验失败: 期望 sha256={} 实际 sha256={}",
+                    expected.trim(),
+                    actual
+                ),
+                true,
+            ));
+        }
+    }
+    Ok(())
+}
+
+// 统一的下载辅助：先写入 dest.partial，成功并校验通过后再重命名为 dest
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+async fn download_with_retry(
+    url: &str,
+    dest: &std::path::Path,
+    opts: &DownloadOptions<'_>,
+) -> Result<(), String> {
+    let _task = BackgroundTaskGuard::begin();
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(opts.connect_timeout)
+        .timeout(opts.timeout);
+    if let Some(raw) = &opts.proxy {
+        let (proxy, display) = build_download_proxy(raw)?;
+        append_log_line(
+            backend_log_path(),
+            &format!("[meta] download_proxy={}", display),
+        );
+        builder = builder.proxy(proxy);
+    }
+    let client = builder
+        .build()
+        .map_err(|e| format!("创建下载客户端失败: {}", e))?;
+    if let Some(dir) = dest.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("创建目录失败 {:?}: {}", dir, e))?;
+    }
+    let partial = PathBuf::from(format!("{}.partial", dest.to_string_lossy()));
+    let _partials = PartialCleanup(vec![partial.clone()]);
+    let mut backoff = opts.backoff;
+    let mut last_err = String::new();
+    for attempt in 0..=opts.retries {
+        if attempt > 0 {
+            append_log_line(
+                backend_log_path(),
+                &format!(
+                    "[meta] download_retry attempt={} url={} last_error={}",
+                    attempt, url, last_err
+                ),
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+        }
+        if background_cancelled() {
+            return Err("应用正在退出，已中止下载".to_string());
+        }
+        match download_once(&client, url, &partial, opts).await {
+            Ok(()) => {
+                return std::fs::rename(&partial, dest)
+                    .map_err(|e| format!("写入文件失败 {:?}: {}", dest, e));
+            }
+            Err((e, false)) => return Err(e),
+            Err((e, true)) => last_err = e,
+        }
+    }
+    Err(format!("已重试 {} 次仍失败: {}", opts.retries, last_err))
+}
+
 #[cfg(target_os = "windows")]
-async fn ensure_ffmpeg_binaries(resource_dir: &PathBuf) -> Result<(), String> {
+async fn ensure_ffmpeg_binaries(
+    resource_dir: &PathBuf,
+    proxy: Option<String>,
+    progress: &ProgressRegistry,
+) -> Result<(), String> {
     let ffmpeg_path = resource_dir.join("ffmpeg.exe");
     let ffprobe_path = resource_dir.join("ffprobe.exe");
     if ffmpeg_path.exists() && ffprobe_path.exists() {
         return Ok(());
     }
-    if let (Ok(ff_in_path), Ok(fp_in_path)) = (which::which("ffmpeg.exe"), which::which("ffprobe.exe")) {
+    // 离线放置约定：SACV_FFMPEG_DIR 指向的目录已有 ffmpeg/ffprobe 时跳过所有网络操作（该目录会加入后端 PATH）
+    if let Some(dir) = offline_ffmpeg_dir() {
+        if dir.join("ffmpeg.exe").exists() && dir.join("ffprobe.exe").exists() {
+            return Ok(());
+        }
+    }
+    if let (Ok(ff_in_path), Ok(fp_in_path)) =
+        (which::which("ffmpeg.exe"), which::which("ffprobe.exe"))
+    {
         if let Err(e) = std::fs::create_dir_all(&resource_dir) {
             return Err(format!("创建资源目录失败: {}", e));
         }
         if let Err(e) = std::fs::copy(&ff_in_path, &ffmpeg_path) {
-            return Err(format!("复制ffmpeg失败 {:?} -> {:?}: {}", ff_in_path, ffmpeg_path, e));
+            return Err(format!(
+                "复制ffmpeg失败 {:?} -> {:?}: {}",
+                ff_in_path, ffmpeg_path, e
+            ));
         }
         if let Err(e) = std::fs::copy(&fp_in_path, &ffprobe_path) {
-            return Err(format!("复制ffprobe失败 {:?} -> {:?}: {}", fp_in_path, ffprobe_path, e));
+            return Err(format!(
+                "复制ffprobe失败 {:?} -> {:?}: {}",
+                fp_in_path, ffprobe_path, e
+            ));
         }
         return Ok(());
     }
-    let url = std::env::var("FFMPEG_WIN_ZIP_URL").ok().unwrap_or_else(|| {
-        "https://www.gyan.dev/ffmpeg/builds/ffmpeg-release-essentials.zip".to_string()
-    });
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(60))
-        .build()
-        .map_err(|e| format!("创建下载客户端失败: {}", e))?;
-    let resp = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("下载FFmpeg压缩包失败: {}", e))?;
-    if !resp.status().is_success() {
-        return Err(format!("下载FFmpeg压缩包返回状态异常: {}", resp.status()));
+    let manual_hint = format!(
+        "请手动将 ffmpeg.exe 与 ffprobe.exe 放置到 {} 或环境变量 SACV_FFMPEG_DIR 指向的目录",
+        resource_dir.to_string_lossy()
+    );
+    if std::env::var("SACV_ALLOW_DOWNLOAD").ok().as_deref() == Some("0") {
+        return Err(format!(
+            "未找到 FFmpeg，且已禁用联网下载（SACV_ALLOW_DOWNLOAD=0）；{}",
+            manual_hint
+        ));
     }
-    let bytes = resp
-        .bytes()
-        .await
-        .map_err(|e| format!("读取FFmpeg压缩包内容失败: {}", e))?;
-    let cursor = std::io::Cursor::new(bytes);
-    let mut archive =
-        ZipArchive::new(cursor).map_err(|e| format!("解析FFmpeg压缩包失败: {}", e))?;
-
-    let mut found_ffmpeg = false;
-    let mut found_ffprobe = false;
-
-    for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .map_err(|e| format!("读取压缩包文件失败: {}", e))?;
-        let name = file.name().to_string();
-        let is_ffmpeg = name.ends_with("/bin/ffmpeg.exe")
-            || name.ends_with("\\bin\\ffmpeg.exe")
-            || name.ends_with("ffmpeg.exe");
-        let is_ffprobe = name.ends_with("/bin/ffprobe.exe")
-            || name.ends_with("\\bin\\ffprobe.exe")
-            || name.ends_with("ffprobe.exe");
-
-        if is_ffmpeg || is_ffprobe {
-            let out_path = if is_ffmpeg {
-                ffmpeg_path.clone()
-            } else {
-                ffprobe_path.clone()
-            };
-            // 确保资源目录存在
-            if let Err(e) = std::fs::create_dir_all(&resource_dir) {
-                return Err(format!("创建资源目录失败: {}", e));
-            }
-            let mut out_file = std::fs::File::create(&out_path)
-                .map_err(|e| format!("创建文件失败 {:?}: {}", out_path, e))?;
-            let mut buf = Vec::new();
-            file.read_to_end(&mut buf)
-                .map_err(|e| format!("读取压缩包条目失败: {}", e))?;
-            out_file
-                .write_all(&buf)
-                .map_err(|e| format!("写入文件失败 {:?}: {}", out_path, e))?;
-            if is_ffmpeg {
-                found_ffmpeg = true;
-            } else {
-                found_ffprobe = true;
+    let _task = BackgroundTaskGuard::begin();
+    let task = ProgressTask::begin(progress, "ffmpeg_download");
+    let on_progress = |downloaded: u64, total: Option<u64>| task.update(downloaded, total);
+    let result: Result<(), String> = async {
+        let url = std::env::var("FFMPEG_WIN_ZIP_URL").ok().unwrap_or_else(|| {
+            "https://www.gyan.dev/ffmpeg/builds/ffmpeg-release-essentials.zip".to_string()
+        });
+        let zip_download_path = resource_dir.join("ffmpeg-download.zip");
+        let opts = DownloadOptions {
+            sha256: std::env::var("FFMPEG_WIN_ZIP_SHA256")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            proxy,
+            on_progress: Some(&on_progress),
+            ..DownloadOptions::default()
+        };
+        download_with_retry(&url, &zip_download_path, &opts)
+            .await
+            .map_err(|e| {
+                format!(
+                    "下载FFmpeg压缩包失败: {}；如处于离线环境，{}",
+                    e, manual_hint
+                )
+            })?;
+        task.set_message("extracting");
+        // 解压结束（无论成败）后删除下载的压缩包
+        let _zip_cleanup = PartialCleanup(vec![zip_download_path.clone()]);
+        let zip_file = std::fs::File::open(&zip_download_path)
+            .map_err(|e| format!("读取FFmpeg压缩包内容失败: {}", e))?;
+        let mut archive =
+            ZipArchive::new(zip_file).map_err(|e| format!("解析FFmpeg压缩包失败: {}", e))?;
+
+        let ffmpeg_partial = resource_dir.join("ffmpeg.exe.partial");
+        let ffprobe_partial = resource_dir.join("ffprobe.exe.partial");
+        let _partials = PartialCleanup(vec![ffmpeg_partial.clone(), ffprobe_partial.clone()]);
+        let mut found_ffmpeg = false;
+        let mut found_ffprobe = false;
+
+        for i in 0..archive.len() {
+            if background_cancelled() {
+                return Err("应用正在退出，已中止FFmpeg解压".to_string());
+            }
+            let mut file = archive
+                .by_index(i)
+                .map_err(|e| format!("读取压缩包文件失败: {}", e))?;
+            let name = file.name().to_string();
+            let is_ffmpeg = name.ends_with("/bin/ffmpeg.exe")
+                || name.ends_with("\\bin\\ffmpeg.exe")
+                || name.ends_with("ffmpeg.exe");
+            let is_ffprobe = name.ends_with("/bin/ffprobe.exe")
+                || name.ends_with("\\bin\\ffprobe.exe")
+                || name.ends_with("ffprobe.exe");
+
+            if is_ffmpeg || is_ffprobe {
+                let out_path = if is_ffmpeg {
+                    ffmpeg_partial.clone()
+                } else {
+                    ffprobe_partial.clone()
+                };
+                // 确保资源目录存在
+                if let Err(e) = std::fs::create_dir_all(&resource_dir) {
+                    return Err(format!("创建资源目录失败: {}", e));
+                }
+                let mut out_file = std::fs::File::create(&out_path)
+                    .map_err(|e| format!("创建文件失败 {:?}: {}", out_path, e))?;
+                let mut buf = Vec::new();
+                file.read_to_end(&mut buf)
+                    .map_err(|e| format!("读取压缩包条目失败: {}", e))?;
+                out_file
+                    .write_all(&buf)
+                    .map_err(|e| format!("写入文件失败 {:?}: {}", out_path, e))?;
+                if is_ffmpeg {
+                    found_ffmpeg = true;
+                } else {
+                    found_ffprobe = true;
+                }
+            }
+            if found_ffmpeg && found_ffprobe {
+                break;
             }
         }
-        if found_ffmpeg && found_ffprobe {
-            break;
+
+        if !found_ffmpeg || !found_ffprobe {
+            return Err("压缩包中未找到 ffmpeg.exe 或 ffprobe.exe".to_string());
+        }
+        std::fs::rename(&ffmpeg_partial, &ffmpeg_path)
+            .map_err(|e| format!("写入文件失败 {:?}: {}", ffmpeg_path, e))?;
+        std::fs::rename(&ffprobe_partial, &ffprobe_path)
+            .map_err(|e| format!("写入文件失败 {:?}: {}", ffprobe_path, e))?;
+        // 冒烟测试：架构不匹配（如 ARM 设备下载到 x86 构建）时解压成功但运行即 0xc000007b，放置后立即验证
+        for tool in [&ffmpeg_path, &ffprobe_path] {
+            if let Err(e) = ffmpeg_tool_smoke_test(tool) {
+                let _ = std::fs::remove_file(&ffmpeg_path);
+                let _ = std::fs::remove_file(&ffprobe_path);
+                append_log_line(
+                    backend_log_path(),
+                    &format!(
+                        "[error] ffmpeg_smoke_test_failed tool={:?} error={}",
+                        tool, e
+                    ),
+                );
+                return Err(format!(
+                    "下载的 FFmpeg 无法运行（可能架构不匹配）: {}；{}",
+                    e, manual_hint
+                ));
+            }
         }
+        Ok(())
     }
+    .await;
+    task.finish(&result);
+    result
+}
 
-    if !found_ffmpeg || !found_ffprobe {
-        return Err("压缩包中未找到 ffmpeg.exe 或 ffprobe.exe".to_string());
+// 执行 `<tool> -version` 确认二进制可在本机运行
+#[cfg(target_os = "windows")]
+fn ffmpeg_tool_smoke_test(tool: &std::path::Path) -> Result<(), String> {
+    let mut cmd = Command::new(tool);
+    cmd.arg("-version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let status = apply_windows_no_window(cmd)
+        .status()
+        .map_err(|e| format!("无法执行 {:?}: {}", tool, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{:?} 退出码异常: {:?}", tool, status.code()))
+    }
+}
+
+// 按 zip 条目清单逐一比对解压结果（文件存在且大小一致），防止中途截断（如磁盘满）的目录被误判为有效
+#[cfg(target_os = "windows")]
+fn verify_extracted_backend(
+    zip_path: &std::path::Path,
+    extracted_dir: &std::path::Path,
+) -> Result<(), String> {
+    let file = std::fs::File::open(zip_path).map_err(|e| format!("打开后端压缩包失败: {}", e))?;
+    let mut zip = ZipArchive::new(file).map_err(|e| format!("读取后端压缩包失败: {}", e))?;
+    let mut missing = 0usize;
+    let mut first_bad: Option<String> = None;
+    for i in 0..zip.len() {
+        let entry = match zip.by_index(i) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+        if entry.is_dir() {
+            continue;
+        }
+        let rel = match entry.enclosed_name() {
+            Some(p) => p.to_path_buf(),
+            None => continue,
+        };
+        let ok = std::fs::metadata(extracted_dir.join(&rel))
+            .map(|m| m.len() == entry.size())
+            .unwrap_or(false);
+        if !ok {
+            missing += 1;
+            if first_bad.is_none() {
+                first_bad = Some(rel.to_string_lossy().to_string());
+            }
+        }
+    }
+    match first_bad {
+        None => Ok(()),
+        Some(name) => Err(format!(
+            "解压后的后端不完整：{} 个文件缺失或大小不符（如 {}）",
+            missing, name
+        )),
     }
-    Ok(())
 }
 
 #[cfg(target_os = "windows")]
@@ -413,8 +1539,12 @@ fn ensure_backend_executable_available(
         let d = mt.duration_since(std::time::UNIX_EPOCH).ok()?;
         Some(format!("{}.{}", d.as_secs(), d.subsec_nanos()))
     };
-    let read_stamp = || -> Option<String> {
-        std::fs::read_to_string(&stamp_path).ok().map(|s| s.trim().to_string())
+    // stamp 首行为压缩包修改时间，次行 verified 表示解压后已按清单校验完整
+    let read_stamp = || -> Option<(String, bool)> {
+        let content = std::fs::read_to_string(&stamp_path).ok()?;
+        let mut lines = content.lines().map(|l| l.trim());
+        let stamp = lines.next()?.to_string();
+        Some((stamp, lines.next() == Some("verified")))
     };
     let should_refresh = || -> bool {
         if !zip_path.exists() {
@@ -425,18 +1555,36 @@ fn ensure_backend_executable_available(
             None => return false,
         };
         match read_stamp() {
-            Some(got) if got == want => false,
+            Some((got, _)) if got == want => false,
             _ => true,
         }
     };
 
+    // 解压时已校验过（stamp 带 verified）直接信任；旧版本写入的 stamp 没有该标记，补校验一次并记入 stamp
+    let is_complete = || -> bool {
+        if !zip_path.exists() || matches!(read_stamp(), Some((_, true))) {
+            return true;
+        }
+        match verify_extracted_backend(&zip_path, &extracted_backend_dir) {
+            Ok(()) => {
+                if let Some(stamp) = zip_stamp() {
+                    let _ = std::fs::write(&stamp_path, format!("{}\nverified\n", stamp));
+                }
+                true
+            }
+            Err(e) => {
+                append_log_line(backend_log_path(), &format!("[warn] {}，将重新解压", e));
+                false
+            }
+        }
+    };
     if let Some(exe) = is_valid_backend_root(&extracted_backend_dir) {
-        if !should_refresh() {
+        if !should_refresh() && is_complete() {
             return Ok(exe);
         }
     }
     if let Some(exe) = is_valid_backend_root(&nested_backend_dir) {
-        if !should_refresh() {
+        if !should_refresh() && is_complete() {
             return Ok(exe);
         }
     }
@@ -447,66 +1595,202 @@ fn ensure_backend_executable_available(
         return Ok(extracted_backend_dir.join("superAutoCutVideoBackend.exe"));
     }
 
-    let _ = std::fs::create_dir_all(&app_data_dir);
-    if extracted_backend_dir.exists() {
-        let _ = std::fs::remove_dir_all(&extracted_backend_dir);
-    }
-    let _ = std::fs::create_dir_all(&extracted_backend_dir);
+    let _task = BackgroundTaskGuard::begin();
+    let task = ProgressTask::begin(
+        &_app_handle.state::<AppState>().progress_tasks,
+        "backend_extract",
+    );
+    let result = (|| -> Result<PathBuf, String> {
+        let _ = std::fs::create_dir_all(&app_data_dir);
+        if extracted_backend_dir.exists() {
+            let _ = std::fs::remove_dir_all(&extracted_backend_dir);
+        }
+        // 先解压到 .partial 目录，成功后再整体重命名，避免中途退出留下半截后端
+        let partial_backend_dir = app_data_dir.join("superAutoCutVideoBackend.partial");
+        if partial_backend_dir.exists() {
+            let _ = std::fs::remove_dir_all(&partial_backend_dir);
+        }
+        let _ = std::fs::create_dir_all(&partial_backend_dir);
+        let _partials = PartialCleanup(vec![partial_backend_dir.clone()]);
 
-    let mut zip_extract_ok = false;
-    if let Ok(file) = std::fs::File::open(&zip_path) {
-        if let Ok(mut zip) = ZipArchive::new(file) {
-            if zip.extract(&extracted_backend_dir).is_ok() {
-                zip_extract_ok = true;
+        let mut zip_extract_ok = false;
+        if let Ok(file) = std::fs::File::open(&zip_path) {
+            if let Ok(mut zip) = ZipArchive::new(file) {
+                task.update(0, Some(zip.len() as u64));
+                if zip.extract(&partial_backend_dir).is_ok() {
+                    zip_extract_ok = true;
+                }
             }
         }
-    }
-    if !zip_extract_ok {
-        let zip_s = zip_path.to_string_lossy().to_string();
-        let out_dir_s = extracted_backend_dir.to_string_lossy().to_string();
-        let zip_q = zip_s.replace('\'', "''");
-        let out_q = out_dir_s.replace('\'', "''");
-        let cmd = format!(
-            "Expand-Archive -LiteralPath '{}' -DestinationPath '{}' -Force",
-            zip_q, out_q
-        );
-        let status = Command::new("powershell")
-            .creation_flags(0x08000000)
-            .arg("-NoLogo")
-            .arg("-NoProfile")
-            .arg("-NonInteractive")
-            .arg("-WindowStyle")
-            .arg("Hidden")
-            .arg("-Command")
-            .arg(cmd)
-            .status()
-            .map_err(|e| format!("调用 PowerShell 解压失败: {}", e))?;
-        if !status.success() {
-            return Err(format!(
-                "解压后端ZIP包失败: zip={} out={} code={:?}",
-                zip_path.to_string_lossy(),
-                extracted_backend_dir.to_string_lossy(),
-                status.code()
-            ));
+        if !zip_extract_ok && !background_cancelled() {
+            let zip_s = zip_path.to_string_lossy().to_string();
+            let out_dir_s = partial_backend_dir.to_string_lossy().to_string();
+            let zip_q = zip_s.replace('\'', "''");
+            let out_q = out_dir_s.replace('\'', "''");
+            let cmd = format!(
+                "Expand-Archive -LiteralPath '{}' -DestinationPath '{}' -Force",
+                zip_q, out_q
+            );
+            let status = Command::new("powershell")
+                .creation_flags(0x08000000)
+                .arg("-NoLogo")
+                .arg("-NoProfile")
+                .arg("-NonInteractive")
+                .arg("-WindowStyle")
+                .arg("Hidden")
+                .arg("-Command")
+                .arg(cmd)
+                .status()
+                .map_err(|e| format!("调用 PowerShell 解压失败: {}", e))?;
+            if !status.success() {
+                return Err(format!(
+                    "解压后端ZIP包失败: zip={} out={} code={:?}",
+                    zip_path.to_string_lossy(),
+                    partial_backend_dir.to_string_lossy(),
+                    status.code()
+                ));
+            }
+        }
+        if background_cancelled() {
+            return Err("应用正在退出，已中止后端解压".to_string());
+        }
+        verify_extracted_backend(&zip_path, &partial_backend_dir)?;
+        std::fs::rename(&partial_backend_dir, &extracted_backend_dir)
+            .map_err(|e| format!("移动解压后的后端目录失败: {}", e))?;
+        if let Some(stamp) = zip_stamp() {
+            let _ = std::fs::write(&stamp_path, format!("{}\nverified\n", stamp));
+        }
+
+        if let Some(exe) = is_valid_backend_root(&extracted_backend_dir) {
+            return Ok(exe);
+        }
+        if let Some(exe) = is_valid_backend_root(&nested_backend_dir) {
+            return Ok(exe);
+        }
+        if extracted_backend_dir.exists() {
+            let _ = std::fs::remove_dir_all(&extracted_backend_dir);
         }
+
+        Err("解压后未找到 superAutoCutVideoBackend.exe".to_string())
+    })();
+    task.finish(&result);
+    result
+}
+
+// 解析后端以 JSON 行输出的结构化事件（如 {"event":"progress","pct":42}），普通文本行返回 None
+fn parse_backend_event_line(line: &str) -> Option<serde_json::Value> {
+    let trimmed = line.trim();
+    if !trimmed.starts_with('{') {
+        return None;
     }
-    if let Some(stamp) = zip_stamp() {
-        let _ = std::fs::write(&stamp_path, stamp);
+    let v: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+    let event = v.get("event")?.as_str()?;
+    if event.is_empty() {
+        return None;
     }
+    Some(v)
+}
 
-    if let Some(exe) = is_valid_backend_root(&extracted_backend_dir) {
-        return Ok(exe);
+fn backend_log_path() -> PathBuf {
+    std::env::temp_dir().join("super_auto_cut_backend.log")
+}
+
+fn app_log_path() -> PathBuf {
+    std::env::temp_dir().join("super_auto_cut_app.log")
+}
+
+// 应用日志超过该大小时轮转为 .1（只保留一份旧日志）
+const APP_LOG_MAX_BYTES: u64 = 5 * 1024 * 1024;
+
+fn write_app_log(level: &str, msg: &str) {
+    let path = app_log_path();
+    if std::fs::metadata(&path).map_or(false, |m| m.len() > APP_LOG_MAX_BYTES) {
+        let _ = std::fs::rename(
+            &path,
+            PathBuf::from(format!("{}.1", path.to_string_lossy())),
+        );
     }
-    if let Some(exe) = is_valid_backend_root(&nested_backend_dir) {
-        return Ok(exe);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    append_log_line(
+        path,
+        &format!(
+            "[{}.{:03}] [{}] {}",
+            now.as_secs(),
+            now.subsec_millis(),
+            level,
+            msg
+        ),
+    );
+}
+
+// 记录启动元数据：写入后端日志，同时收集到内存供 get_last_boot_trace 结构化返回
+fn log_meta(trace: &Mutex<HashMap<String, String>>, key: &str, value: impl std::fmt::Display) {
+    let value = value.to_string();
+    append_log_line(backend_log_path(), &format!("[meta] {}={}", key, value));
+    trace.lock().unwrap().insert(key.to_string(), value);
+}
+
+// 本次运行中出现过的 boot_token，写日志时逐字替换（后端自报的 token 未必是 64 位 hex）
+static KNOWN_BOOT_TOKENS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+const MAX_KNOWN_BOOT_TOKENS: usize = 8;
+
+fn register_boot_token(token: &str) {
+    if token.len() < 8 {
+        return;
     }
-    if extracted_backend_dir.exists() {
-        let _ = std::fs::remove_dir_all(&extracted_backend_dir);
+    let mut tokens = KNOWN_BOOT_TOKENS.lock().unwrap();
+    if !tokens.iter().any(|t| t == token) {
+        if tokens.len() >= MAX_KNOWN_BOOT_TOKENS {
+            tokens.remove(0);
+        }
+        tokens.push(token.to_string());
+    }
+}
+
+// 把 key 之后的值（到空白、引号或分隔符为止）替换为 ***
+fn redact_after_key(text: &str, key: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find(key) {
+        let (head, tail) = rest.split_at(idx + key.len());
+        out.push_str(head);
+        let value_len = tail
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '&' | ',' | ';'))
+            .unwrap_or(tail.len());
+        if value_len > 0 {
+            out.push_str("***");
+        }
+        rest = &tail[value_len..];
     }
+    out.push_str(rest);
+    out
+}
 
-    Err("解压后未找到 superAutoCutVideoBackend.exe".to_string())
+// 脱敏：已知的 boot_token 值，以及 SACV_BOOT_TOKEN=、boot_token=、JSON 中 "boot_token" 字段之后的值替换为 ***；
+// 不按十六进制串长度泛化匹配，避免误伤日志中的 SHA-256 校验值
+fn redact_secrets(text: &str) -> String {
+    let mut text = text.to_string();
+    for token in KNOWN_BOOT_TOKENS.lock().unwrap().iter() {
+        if text.contains(token.as_str()) {
+            text = text.replace(token.as_str(), "***");
+        }
+    }
+    for key in [
+        "SACV_BOOT_TOKEN=",
+        "boot_token=",
+        "\"boot_token\":\"",
+        "\"boot_token\": \"",
+    ] {
+        if text.contains(key) {
+            text = redact_after_key(&text, key);
+        }
+    }
+    text
 }
 
+// 追加一行日志（写入前脱敏，后端 stdout/stderr 可能打印 token 等敏感信息）
 fn append_log_line(path: PathBuf, line: &str) {
     if let Ok(mut file) = std::fs::OpenOptions::new()
         .create(true)
@@ -514,258 +1798,817 @@ fn append_log_line(path: PathBuf, line: &str) {
         .open(&path)
     {
         use std::io::Write;
-        let _ = writeln!(file, "{}", line);
+        let _ = writeln!(file, "{}", redact_secrets(line));
     }
 }
 
+// 后端临时目录：SACV_BACKEND_TMPDIR 优先，否则放在应用缓存目录下
+fn backend_tmp_dir(app_handle: &AppHandle) -> PathBuf {
+    std::env::var("SACV_BACKEND_TMPDIR")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| app_handle.path().app_cache_dir().ok())
+        .unwrap_or_else(std::env::temp_dir)
+        .join("super_auto_cut_backend_tmp")
+}
+
+fn backend_port_cache_path() -> PathBuf {
+    std::env::temp_dir().join("super_auto_cut_backend.port")
+}
+
+fn read_cached_backend_port() -> Option<u16> {
+    std::fs::read_to_string(backend_port_cache_path())
+        .ok()
+        .and_then(|s| s.trim().parse::<u16>().ok())
+        .filter(|p| *p > 0)
+}
+
+// IPv4 与 IPv6 回环都能 bind 才算可用；系统禁用 IPv6 时（bind ::1 报地址不可用等错误）只看 IPv4
 fn is_port_available(port: u16) -> bool {
-    TcpListener::bind(("127.0.0.1", port)).map(|_l| ()).is_ok()
+    let v4 = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(l) => l,
+        Err(_) => return false,
+    };
+    let v6 = TcpListener::bind((std::net::Ipv6Addr::LOCALHOST, port));
+    drop(v4);
+    match v6 {
+        Ok(_) => true,
+        Err(e) => e.kind() != std::io::ErrorKind::AddrInUse,
+    }
 }
 
-fn choose_backend_port(is_dev_mode: bool) -> u16 {
+const DEFAULT_DEV_PORT: u16 = 8000;
+
+// 环境变量 SACV_DEV_PORT 指定的 dev 首选端口（未设置或不合法时为 None）
+fn configured_dev_port() -> Option<u16> {
+    std::env::var("SACV_DEV_PORT")
+        .ok()
+        .and_then(|v| v.trim().parse::<u16>().ok())
+        .filter(|p| *p != 0)
+}
+
+// 在固定端口范围内找第一个可用端口（开发模式优先 SACV_DEV_PORT/8000 段，生产优先 18000 段），不产生副作用
+fn scan_backend_port(is_dev_mode: bool) -> Option<u16> {
     if is_dev_mode {
-        let preferred = 8000;
-        if is_port_available(preferred) {
-            return preferred;
-        }
-        for p in preferred..=preferred + 100 {
-            if is_port_available(p) {
-                return p;
-            }
-        }
-        for p in 18000..=18100 {
-            if is_port_available(p) {
-                return p;
-            }
-        }
-        preferred
+        let preferred = configured_dev_port().unwrap_or(DEFAULT_DEV_PORT);
+        std::iter::once(preferred)
+            .chain(8000..=8100)
+            .chain(18000..=18100)
+            .find(|p| is_port_available(*p))
     } else {
-        for p in 18000..=18100 {
-            if is_port_available(p) {
-                return p;
-            }
-        }
-        for p in 8000..=8100 {
-            if is_port_available(p) {
-                return p;
+        (18000..=18100)
+            .chain(8000..=8100)
+            .find(|p| is_port_available(*p))
+    }
+}
+
+// 查询监听指定 TCP 端口的进程，返回"进程名 (pid=N)"，用于端口冲突提示；查询失败返回 None
+fn describe_port_owner(port: u16) -> Option<String> {
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new("netstat");
+        cmd.args(["-ano", "-p", "TCP"]);
+        let out = apply_windows_no_window(cmd).output().ok()?;
+        let text = String::from_utf8_lossy(&out.stdout).to_string();
+        let suffix = format!(":{}", port);
+        // "  TCP    127.0.0.1:18000    0.0.0.0:0    LISTENING    1234"
+        let pid = text.lines().find_map(|line| {
+            let cols: Vec<&str> = line.split_whitespace().collect();
+            if cols.len() >= 5 && cols[1].ends_with(&suffix) && cols[3] == "LISTENING" {
+                cols[4].parse::<u32>().ok()
+            } else {
+                None
             }
+        })?;
+        let mut cmd = Command::new("tasklist");
+        cmd.args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"]);
+        let name = apply_windows_no_window(cmd).output().ok().and_then(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .next()
+                .and_then(|l| l.split(',').next())
+                .map(|n| n.trim().trim_matches('"').to_string())
+                .filter(|n| n.to_lowercase().ends_with(".exe"))
+        });
+        Some(match name {
+            Some(n) => format!("{} (pid={})", n, pid),
+            None => format!("pid={}", pid),
+        })
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        // lsof -F 输出逐行字段：p<pid>、c<命令名>
+        let out = Command::new("lsof")
+            .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-Fpc"])
+            .output()
+            .ok()?;
+        let text = String::from_utf8_lossy(&out.stdout).to_string();
+        let pid = text.lines().find_map(|l| l.strip_prefix('p'))?;
+        Some(match text.lines().find_map(|l| l.strip_prefix('c')) {
+            Some(name) => format!("{} (pid={})", name, pid),
+            None => format!("pid={}", pid),
+        })
+    }
+}
+
+fn choose_backend_port(is_dev_mode: bool) -> u16 {
+    if is_dev_mode {
+        scan_backend_port(true).unwrap_or_else(|| configured_dev_port().unwrap_or(DEFAULT_DEV_PORT))
+    } else {
+        if let Some(p) = scan_backend_port(false) {
+            let _ = std::fs::remove_file(backend_port_cache_path());
+            return p;
         }
-        TcpListener::bind(("127.0.0.1", 0))
+        // 固定范围全部被占用：退回系统分配的随机端口，并写入缓存供后续发现逻辑读取
+        let port = TcpListener::bind(("127.0.0.1", 0))
             .ok()
             .and_then(|l| l.local_addr().ok())
             .map(|a| a.port())
-            .unwrap_or(18000)
+            .unwrap_or(18000);
+        let _ = std::fs::write(backend_port_cache_path(), port.to_string());
+        port
     }
 }
 
-// Tauri命令：启动Python后端
-#[tauri::command]
-async fn start_backend(
-    state: State<'_, AppState>,
-    app_handle: AppHandle,
-) -> Result<BackendStatus, String> {
-    let early_log_path = std::env::temp_dir().join("super_auto_cut_backend.log");
-    let _ = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&early_log_path);
-    append_log_line(early_log_path.clone(), "[meta] start_backend invoked");
+#[cfg(target_os = "windows")]
+#[link(name = "kernel32")]
+extern "system" {
+    fn LoadLibraryExW(
+        lp_lib_file_name: *const u16,
+        h_file: *mut std::ffi::c_void,
+        dw_flags: u32,
+    ) -> *mut std::ffi::c_void;
+    fn FreeLibrary(h_lib_module: *mut std::ffi::c_void) -> i32;
+    fn GetDiskFreeSpaceExW(
+        lp_directory_name: *const u16,
+        lp_free_bytes_available_to_caller: *mut u64,
+        lp_total_number_of_bytes: *mut u64,
+        lp_total_number_of_free_bytes: *mut u64,
+    ) -> i32;
+    fn CreateJobObjectW(
+        lp_job_attributes: *mut std::ffi::c_void,
+        lp_name: *const u16,
+    ) -> *mut std::ffi::c_void;
+    fn SetInformationJobObject(
+        h_job: *mut std::ffi::c_void,
+        job_object_information_class: i32,
+        lp_job_object_information: *mut std::ffi::c_void,
+        cb_job_object_information_length: u32,
+    ) -> i32;
+    fn AssignProcessToJobObject(
+        h_job: *mut std::ffi::c_void,
+        h_process: *mut std::ffi::c_void,
+    ) -> i32;
+    fn TerminateJobObject(h_job: *mut std::ffi::c_void, u_exit_code: u32) -> i32;
+    fn CloseHandle(h_object: *mut std::ffi::c_void) -> i32;
+}
 
-    // 最早期并发启动防护：若已有启动流程进行中，则等待其更新状态，避免重复拉起
-    if state.backend_starting.swap(true, Ordering::SeqCst) {
-        for _ in 0..40 {
-            tokio::time::sleep(Duration::from_millis(150)).await;
-            let port = *state.backend_port.lock().unwrap();
-            let boot_token = state.backend_boot_token.lock().unwrap().clone();
-            let process_guard = state.backend_process.lock().unwrap();
-            let running = process_guard.is_some() || port != 0;
-            drop(process_guard);
-            if running {
-                if port != 0 {
-                    println!(
-                        "[backend] 启动中（复用已有启动流程）：http://127.0.0.1:{}",
-                        port
-                    );
-                }
-                return Ok(BackendStatus {
-                    running,
-                    port,
-                    pid: None,
-                    boot_token,
-                });
-            }
+#[cfg(target_os = "windows")]
+#[repr(C)]
+#[derive(Default)]
+struct JobObjectBasicLimitInformation {
+    per_process_user_time_limit: i64,
+    per_job_user_time_limit: i64,
+    limit_flags: u32,
+    minimum_working_set_size: usize,
+    maximum_working_set_size: usize,
+    active_process_limit: u32,
+    affinity: usize,
+    priority_class: u32,
+    scheduling_class: u32,
+}
+
+#[cfg(target_os = "windows")]
+#[repr(C)]
+#[derive(Default)]
+struct JobObjectExtendedLimitInformation {
+    basic_limit_information: JobObjectBasicLimitInformation,
+    io_info: [u64; 6],
+    process_memory_limit: usize,
+    job_memory_limit: usize,
+    peak_process_memory_used: usize,
+    peak_job_memory_used: usize,
+}
+
+// Windows：把后端放进 Job Object（关闭句柄即结束整组进程），返回句柄（以 usize 保存便于跨线程）
+#[cfg(target_os = "windows")]
+fn attach_backend_job(child: &Child) -> Option<usize> {
+    use std::os::windows::io::AsRawHandle;
+    const JOB_OBJECT_EXTENDED_LIMIT_INFORMATION_CLASS: i32 = 9;
+    const JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE: u32 = 0x2000;
+    unsafe {
+        let job = CreateJobObjectW(std::ptr::null_mut(), std::ptr::null());
+        if job.is_null() {
+            return None;
+        }
+        let mut info = JobObjectExtendedLimitInformation::default();
+        info.basic_limit_information.limit_flags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        let ok = SetInformationJobObject(
+            job,
+            JOB_OBJECT_EXTENDED_LIMIT_INFORMATION_CLASS,
+            &mut info as *mut _ as *mut std::ffi::c_void,
+            std::mem::size_of::<JobObjectExtendedLimitInformation>() as u32,
+        );
+        if ok == 0
+            || AssignProcessToJobObject(job, child.as_raw_handle() as *mut std::ffi::c_void) == 0
+        {
+            CloseHandle(job);
+            return None;
         }
-        return Err("后端正在启动中，请稍后重试".to_string());
+        Some(job as usize)
     }
+}
 
-    // 先短暂持锁检查和清理状态，避免并发重复启动
-    {
-        let mut process_guard = state.backend_process.lock().unwrap();
-        if let Some(ref mut child) = *process_guard {
-            match child.try_wait() {
-                Ok(Some(_)) => {
-                    // 进程已退出，清理
-                    *process_guard = None;
-                }
-                Ok(None) => {
-                    // 进程仍在运行
-                    let port = *state.backend_port.lock().unwrap();
-                    let boot_token = state.backend_boot_token.lock().unwrap().clone();
-                    println!(
-                        "[backend] 已在运行：http://127.0.0.1:{} (pid={})",
-                        port,
-                        child.id()
-                    );
-                    return Ok(BackendStatus {
-                        running: true,
-                        port,
-                        pid: Some(child.id()),
-                        boot_token,
-                    });
-                }
-                Err(_) => {
-                    // 检查失败，清理
-                    *process_guard = None;
-                }
-            }
-        }
+#[cfg(target_os = "windows")]
+fn terminate_backend_job(job: usize) {
+    unsafe {
+        TerminateJobObject(job as *mut std::ffi::c_void, 1);
+        CloseHandle(job as *mut std::ffi::c_void);
     }
+}
 
-    let host = "127.0.0.1";
-    let is_dev_mode =
-        cfg!(debug_assertions) || std::env::var("TAURI_DEV").ok().as_deref() == Some("1");
-    let forced_port_opt = std::env::var("SACV_FORCE_PORT")
-        .ok()
-        .and_then(|s| s.parse::<u16>().ok())
-        .filter(|p| *p > 0);
-    if is_dev_mode && forced_port_opt.is_none() {
-        if let Some((p, boot_token)) = discover_existing_backend(host, false).await {
-            *state.backend_port.lock().unwrap() = p;
-            *state.backend_boot_token.lock().unwrap() = boot_token.clone();
-            println!("[backend] 已发现运行中的后端：http://{}:{}", host, p);
-            return Ok(BackendStatus {
-                running: true,
-                port: p,
-                pid: None,
-                boot_token,
-            });
+#[cfg(unix)]
+extern "C" {
+    fn setsid() -> i32;
+    fn kill(pid: i32, sig: i32) -> i32;
+}
+
+// Unix：后端以新 session 启动后 pgid 即其 pid；先 SIGTERM 整组，超时未退出再 SIGKILL
+#[cfg(unix)]
+fn terminate_process_group(child: &mut Child) -> std::io::Result<()> {
+    const SIGTERM: i32 = 15;
+    const SIGKILL: i32 = 9;
+    let pgid = child.id() as i32;
+    if unsafe { kill(-pgid, SIGTERM) } != 0 {
+        return child.kill();
+    }
+    for _ in 0..30 {
+        if let Ok(Some(_)) = child.try_wait() {
+            return Ok(());
         }
+        thread::sleep(Duration::from_millis(100));
     }
-    // 生产环境也尝试发现已运行的后端，避免重复启动
-    if !is_dev_mode && forced_port_opt.is_none() {
-        if let Some((p, boot_token)) = discover_existing_backend_quick(host, true).await {
-            *state.backend_port.lock().unwrap() = p;
-            *state.backend_boot_token.lock().unwrap() = boot_token.clone();
-            println!("[backend] 已发现运行中的后端：http://{}:{}", host, p);
-            return Ok(BackendStatus {
-                running: true,
-                port: p,
-                pid: None,
-                boot_token,
-            });
+    unsafe {
+        kill(-pgid, SIGKILL);
+    }
+    child.kill()
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "objc")]
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {
+    fn objc_getClass(name: *const std::os::raw::c_char) -> *mut std::ffi::c_void;
+    fn sel_registerName(name: *const std::os::raw::c_char) -> *mut std::ffi::c_void;
+    fn objc_msgSend();
+}
+
+// NSActivityUserInitiatedAllowingIdleSystemSleep：阻止 App Nap 与定时器节流，但允许系统空闲睡眠
+#[cfg(target_os = "macos")]
+const NS_ACTIVITY_USER_INITIATED_ALLOWING_IDLE_SYSTEM_SLEEP: u64 = 0x00FF_FFFF & !(1u64 << 20);
+
+// macOS：持有期间通过 NSProcessInfo beginActivityWithOptions:reason: 阻止 App Nap，
+// 避免应用在后台时轮询定时器被节流导致误判超时；Drop 时 endActivity。其它平台为空操作
+struct AppNapGuard {
+    #[cfg(target_os = "macos")]
+    activity: usize,
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn objc_sel(name: &[u8]) -> *mut std::ffi::c_void {
+    sel_registerName(name.as_ptr() as *const std::os::raw::c_char)
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn ns_process_info() -> *mut std::ffi::c_void {
+    type MsgSend0 =
+        unsafe extern "C" fn(*mut std::ffi::c_void, *mut std::ffi::c_void) -> *mut std::ffi::c_void;
+    let send: MsgSend0 = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+    let class = objc_getClass(b"NSProcessInfo\0".as_ptr() as *const std::os::raw::c_char);
+    send(class, objc_sel(b"processInfo\0"))
+}
+
+impl AppNapGuard {
+    fn begin(reason: &str) -> Self {
+        #[cfg(target_os = "macos")]
+        unsafe {
+            use std::ffi::c_void;
+            use std::os::raw::c_char;
+            type MsgSend0 = unsafe extern "C" fn(*mut c_void, *mut c_void) -> *mut c_void;
+            type MsgSendStr =
+                unsafe extern "C" fn(*mut c_void, *mut c_void, *const c_char) -> *mut c_void;
+            type MsgSendBegin =
+                unsafe extern "C" fn(*mut c_void, *mut c_void, u64, *mut c_void) -> *mut c_void;
+            let raw = objc_msgSend as unsafe extern "C" fn();
+            let send0: MsgSend0 = std::mem::transmute(raw);
+            let send_str: MsgSendStr = std::mem::transmute(raw);
+            let send_begin: MsgSendBegin = std::mem::transmute(raw);
+            let reason_c = std::ffi::CString::new(reason).unwrap_or_default();
+            let ns_reason = send_str(
+                objc_getClass(b"NSString\0".as_ptr() as *const c_char),
+                objc_sel(b"stringWithUTF8String:\0"),
+                reason_c.as_ptr(),
+            );
+            let activity = send_begin(
+                ns_process_info(),
+                objc_sel(b"beginActivityWithOptions:reason:\0"),
+                NS_ACTIVITY_USER_INITIATED_ALLOWING_IDLE_SYSTEM_SLEEP,
+                ns_reason,
+            );
+            // 返回的 token 为 autorelease 对象，持有期间需 retain
+            if !activity.is_null() {
+                send0(activity, objc_sel(b"retain\0"));
+            }
+            AppNapGuard {
+                activity: activity as usize,
+            }
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = reason;
+            AppNapGuard {}
         }
     }
+}
 
-    // 获取资源目录路径（并准备后备路径：与应用同级 resources 目录）
-    let resource_dir = match app_handle.path().resource_dir() {
-        Ok(p) => p,
-        Err(_e) => {
-            let exe_dir_fallback = std::env::current_exe()
-                .ok()
-                .and_then(|p| p.parent().map(|d| d.to_path_buf()));
-            if let Some(dir) = exe_dir_fallback {
-                dir.join("resources")
-            } else {
-                std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")).join("resources")
+impl Drop for AppNapGuard {
+    fn drop(&mut self) {
+        #[cfg(target_os = "macos")]
+        unsafe {
+            use std::ffi::c_void;
+            if self.activity == 0 {
+                return;
             }
+            type MsgSend0 = unsafe extern "C" fn(*mut c_void, *mut c_void) -> *mut c_void;
+            type MsgSend1 = unsafe extern "C" fn(*mut c_void, *mut c_void, *mut c_void);
+            let raw = objc_msgSend as unsafe extern "C" fn();
+            let send0: MsgSend0 = std::mem::transmute(raw);
+            let send1: MsgSend1 = std::mem::transmute(raw);
+            let activity = self.activity as *mut c_void;
+            send1(ns_process_info(), objc_sel(b"endActivity:\0"), activity);
+            send0(activity, objc_sel(b"release\0"));
         }
+    }
+}
+
+#[cfg(target_os = "windows")]
+#[link(name = "shell32")]
+extern "system" {
+    fn SHAddToRecentDocs(u_flags: u32, pv: *const std::ffi::c_void);
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {}
+
+// 是否把选择的文件加入系统“最近使用”（配置 add_to_recent，默认开启；隐私敏感用户可关闭）
+fn add_to_recent_enabled(app: &AppHandle) -> bool {
+    load_app_config(app)
+        .get("add_to_recent")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+// 把文件注册到系统最近使用列表：Windows 跳转列表（SHAddToRecentDocs）、macOS 最近项（NSDocumentController，须在主线程调用）、
+// Linux recently-used.xbel；失败只记录日志
+fn add_to_recent_documents(app: &AppHandle, path: &std::path::Path) {
+    if !add_to_recent_enabled(app) {
+        return;
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        const SHARD_PATHW: u32 = 0x0000_0003;
+        let wide: Vec<u16> = path
+            .as_os_str()
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        unsafe { SHAddToRecentDocs(SHARD_PATHW, wide.as_ptr() as *const std::ffi::c_void) };
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let path_c = match std::ffi::CString::new(path.to_string_lossy().as_bytes()) {
+            Ok(p) => p,
+            Err(_) => return,
+        };
+        let _ = app.run_on_main_thread(move || unsafe {
+            use std::ffi::c_void;
+            use std::os::raw::c_char;
+            type MsgSend0 = unsafe extern "C" fn(*mut c_void, *mut c_void) -> *mut c_void;
+            type MsgSend1 =
+                unsafe extern "C" fn(*mut c_void, *mut c_void, *mut c_void) -> *mut c_void;
+            type MsgSendStr =
+                unsafe extern "C" fn(*mut c_void, *mut c_void, *const c_char) -> *mut c_void;
+            let raw = objc_msgSend as unsafe extern "C" fn();
+            let send0: MsgSend0 = std::mem::transmute(raw);
+            let send1: MsgSend1 = std::mem::transmute(raw);
+            let send_str: MsgSendStr = std::mem::transmute(raw);
+            let ns_path = send_str(
+                objc_getClass(b"NSString\0".as_ptr() as *const c_char),
+                objc_sel(b"stringWithUTF8String:\0"),
+                path_c.as_ptr(),
+            );
+            if ns_path.is_null() {
+                return;
+            }
+            let url = send1(
+                objc_getClass(b"NSURL\0".as_ptr() as *const c_char),
+                objc_sel(b"fileURLWithPath:\0"),
+                ns_path,
+            );
+            let controller = send0(
+                objc_getClass(b"NSDocumentController\0".as_ptr() as *const c_char),
+                objc_sel(b"sharedDocumentController\0"),
+            );
+            if !url.is_null() && !controller.is_null() {
+                send1(controller, objc_sel(b"noteNewRecentDocumentURL:\0"), url);
+            }
+        });
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let path = path.to_path_buf();
+        thread::spawn(move || {
+            if let Err(e) = add_to_recently_used_xbel(&path) {
+                app_log!(warn, "[app] 写入最近使用列表失败: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn xml_escape_attr(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// Linux：按 freedesktop 书签规范把文件追加到 $XDG_DATA_HOME/recently-used.xbel（已存在的条目不重复添加），
+// 先写临时文件再重命名，避免写坏其它应用共用的文件
+#[cfg(all(unix, not(target_os = "macos")))]
+fn add_to_recently_used_xbel(path: &std::path::Path) -> Result<(), String> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("share")))
+        .ok_or_else(|| "无法确定用户数据目录".to_string())?;
+    let xbel_path = data_home.join("recently-used.xbel");
+    let href = tauri::Url::from_file_path(path)
+        .map_err(|_| format!("路径无法转换为 URL: {:?}", path))?
+        .to_string();
+    let href = xml_escape_attr(&href);
+    let existing = std::fs::read_to_string(&xbel_path).unwrap_or_default();
+    if existing.contains(&format!("href=\"{}\"", href)) {
+        return Ok(());
+    }
+    let now = utc_time::format_utc_rfc3339(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64,
+    );
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let mime = match ext.as_str() {
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "mkv" => "video/x-matroska",
+        "avi" => "video/x-msvideo",
+        "wmv" => "video/x-ms-wmv",
+        "flv" => "video/x-flv",
+        "webm" => "video/webm",
+        "m4v" => "video/x-m4v",
+        _ => "application/octet-stream",
     };
-    let resource_root = {
-        let sub = resource_dir.join("resources");
-        if sub.exists() { sub } else { resource_dir.clone() }
+    let exec = std::env::current_exe()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| "super-auto-cut-video".to_string());
+    let entry = format!(
+        "  <bookmark href=\"{href}\" added=\"{now}\" modified=\"{now}\" visited=\"{now}\">\n    <info>\n      <metadata owner=\"http://freedesktop.org\">\n        <mime:mime-type type=\"{mime}\"/>\n        <bookmark:applications>\n          <bookmark:application name=\"SuperAI 影视剪辑\" exec=\"{exec}\" modified=\"{now}\" count=\"1\"/>\n        </bookmark:applications>\n      </metadata>\n    </info>\n  </bookmark>\n",
+        href = href,
+        now = now,
+        mime = mime,
+        exec = xml_escape_attr(&format!("'{} %u'", exec)),
+    );
+    let content = match existing.rfind("</xbel>") {
+        Some(idx) => format!("{}{}{}", &existing[..idx], entry, &existing[idx..]),
+        None => format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<xbel version=\"1.0\"\n      xmlns:bookmark=\"http://www.freedesktop.org/standards/desktop-bookmarks\"\n      xmlns:mime=\"http://www.freedesktop.org/standards/shared-mime-info\"\n>\n{}</xbel>\n",
+            entry
+        ),
     };
-    let exe_dir_fallback = std::env::current_exe()
-        .ok()
-        .and_then(|p| p.parent().map(|d| d.to_path_buf()));
-    let install_dir = exe_dir_fallback
-        .clone()
-        .or_else(|| resource_dir.parent().map(|p| p.to_path_buf()));
+    std::fs::create_dir_all(&data_home)
+        .map_err(|e| format!("创建目录失败 {:?}: {}", data_home, e))?;
+    let tmp_path = data_home.join("recently-used.xbel.sacv_tmp");
+    std::fs::write(&tmp_path, content).map_err(|e| format!("写入 {:?} 失败: {}", tmp_path, e))?;
+    std::fs::rename(&tmp_path, &xbel_path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        format!("写入 {:?} 失败: {}", xbel_path, e)
+    })
+}
 
-    let force_packaged_backend =
-        std::env::var("FORCE_PACKAGED_BACKEND").ok().as_deref() == Some("1");
-    let backend_zip_path = resource_root.join("superAutoCutVideoBackend.zip");
-    let backend_zip_exists = backend_zip_path.exists();
-    let backend_folder_exe = resource_root
-        .join("superAutoCutVideoBackend")
-        .join("superAutoCutVideoBackend.exe");
-    let backend_folder_exists = backend_folder_exe.exists();
-    let prefer_python_backend =
-        is_dev_mode && !force_packaged_backend && !backend_zip_exists && !backend_folder_exists;
+// Tauri命令：设置选择文件后是否加入系统最近使用列表
+#[tauri::command]
+async fn set_add_to_recent(app_handle: AppHandle, enabled: bool) -> Result<(), String> {
+    update_app_config(&app_handle, |config| {
+        config.insert("add_to_recent".to_string(), serde_json::json!(enabled));
+    })
+}
 
-    append_log_line(
-        early_log_path.clone(),
-        &format!(
-            "[meta] is_dev_mode={} prefer_python_backend={} resource_dir={} resource_root={} backend_zip_exists={}",
-            is_dev_mode,
-            prefer_python_backend,
-            resource_dir.to_string_lossy(),
-            resource_root.to_string_lossy(),
-            backend_zip_exists
-        ),
-    );
+// 查询路径所在磁盘对当前用户可用的字节数；路径不存在时取最近的已存在祖先目录
+fn available_disk_space(path: &std::path::Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        let wide: Vec<u16> = existing
+            .as_os_str()
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        let mut free_to_caller = 0u64;
+        let ok = unsafe {
+            GetDiskFreeSpaceExW(
+                wide.as_ptr(),
+                &mut free_to_caller,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+        return if ok != 0 { Some(free_to_caller) } else { None };
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        // df -Pk 输出 POSIX 格式，第二行第 4 列为可用 KB
+        let output = Command::new("df").arg("-Pk").arg(existing).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        let kb: u64 = text
+            .lines()
+            .nth(1)?
+            .split_whitespace()
+            .nth(3)?
+            .parse()
+            .ok()?;
+        Some(kb * 1024)
+    }
+}
+
+// 检测系统是否安装了 VC++ 2015-2022 运行库（按系统搜索路径试加载）
+#[cfg(target_os = "windows")]
+fn missing_vc_runtime_dlls() -> Vec<String> {
+    use std::os::windows::ffi::OsStrExt;
+    let mut missing = Vec::new();
+    for name in ["vcruntime140.dll", "msvcp140.dll"] {
+        let wide: Vec<u16> = std::ffi::OsStr::new(name)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        let module = unsafe { LoadLibraryExW(wide.as_ptr(), std::ptr::null_mut(), 0) };
+        if module.is_null() {
+            missing.push(name.to_string());
+        } else {
+            unsafe {
+                FreeLibrary(module);
+            }
+        }
+    }
+    missing
+}
 
+// 试加载打包后端依赖的关键 DLL，返回加载失败的 DLL 及修复提示（写入日志）
+fn diagnose_backend_dlls(backend_executable: &std::path::Path) -> Option<String> {
     #[cfg(target_os = "windows")]
-    let extracted_backend_exe = if !prefer_python_backend && backend_zip_exists && !backend_folder_exists {
-        let zip_path = resource_root.join("superAutoCutVideoBackend.zip");
-        if let Ok(app_data_dir) = app_handle.path().app_data_dir() {
-            append_log_line(
-                early_log_path.clone(),
-                &format!("[meta] app_data_dir={}", app_data_dir.to_string_lossy()),
-            );
+    {
+        use std::os::windows::ffi::OsStrExt;
+        // 让被加载 DLL 的依赖从其自身所在目录解析
+        const LOAD_WITH_ALTERED_SEARCH_PATH: u32 = 0x00000008;
+        let internal_dir = backend_executable.parent()?.join("_internal");
+        let mut targets: Vec<PathBuf> = Vec::new();
+        for name in ["vcruntime140.dll", "vcruntime140_1.dll", "python311.dll"] {
+            let bundled = internal_dir.join(name);
+            if bundled.exists() {
+                targets.push(bundled);
+            } else if name != "vcruntime140_1.dll" {
+                // 未随包附带时按系统搜索路径加载（即系统安装的运行库）
+                targets.push(PathBuf::from(name));
+            }
+        }
+        let mut failed: Vec<String> = Vec::new();
+        for target in targets {
+            let wide: Vec<u16> = target
+                .as_os_str()
+                .encode_wide()
+                .chain(std::iter::once(0))
+                .collect();
+            let module = unsafe {
+                LoadLibraryExW(
+                    wide.as_ptr(),
+                    std::ptr::null_mut(),
+                    LOAD_WITH_ALTERED_SEARCH_PATH,
+                )
+            };
+            if module.is_null() {
+                let err = std::io::Error::last_os_error();
+                let name = target
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                failed.push(format!("{}({})", name, err.raw_os_error().unwrap_or(0)));
+            } else {
+                unsafe {
+                    FreeLibrary(module);
+                }
+            }
+        }
+        if failed.is_empty() {
+            return None;
         }
+        let list = failed.join(", ");
         append_log_line(
-            early_log_path.clone(),
-            &format!(
-                "[meta] backend_zip_exists={} backend_zip_path={}",
-                zip_path.exists(),
-                zip_path.to_string_lossy()
-            ),
+            backend_log_path(),
+            &format!("[error] dll_load_failed: {}", list),
         );
-        append_log_line(early_log_path.clone(), "[meta] ensure_backend_executable_available_begin");
-        match ensure_backend_executable_available(&app_handle, &resource_root) {
-            Ok(p) => {
-                append_log_line(
-                    early_log_path.clone(),
-                    &format!(
-                        "[meta] ensure_backend_executable_available_ok={} exists={}",
-                        p.to_string_lossy(),
-                        p.exists()
-                    ),
-                );
-                Some(p)
+        return Some(format!(
+            "无法加载后端依赖的 DLL: {}，请安装 Microsoft Visual C++ 2015-2022 运行库 (x64) 或重新安装应用",
+            list
+        ));
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = backend_executable;
+        None
+    }
+}
+
+// 判断文件头是否为 ELF / Mach-O 可执行格式（含 fat binary），用于在 _internal 中识别需要执行位的文件
+#[cfg(unix)]
+fn is_native_binary(path: &std::path::Path) -> bool {
+    use std::io::Read;
+    let mut magic = [0u8; 4];
+    let ok = std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .is_ok();
+    ok && matches!(
+        magic,
+        [0x7f, b'E', b'L', b'F']
+            | [0xfe, 0xed, 0xfa, 0xce]
+            | [0xfe, 0xed, 0xfa, 0xcf]
+            | [0xce, 0xfa, 0xed, 0xfe]
+            | [0xcf, 0xfa, 0xed, 0xfe]
+            | [0xca, 0xfe, 0xba, 0xbe]
+    )
+}
+
+// 给缺少执行位的文件补上 u+x/g+x/o+x（按原有读权限对应补齐），返回是否做了修改
+#[cfg(unix)]
+fn repair_exec_bit(path: &std::path::Path) -> Result<bool, String> {
+    use std::os::unix::fs::PermissionsExt;
+    let meta = std::fs::metadata(path).map_err(|e| format!("读取权限失败 {:?}: {}", path, e))?;
+    let mode = meta.permissions().mode();
+    if mode & 0o111 != 0 {
+        return Ok(false);
+    }
+    // 可读的主体才补执行位，至少保证属主可执行
+    let new_mode = mode | ((mode & 0o444) >> 2) | 0o100;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(new_mode))
+        .map_err(|e| format!("设置执行权限失败 {:?}: {}", path, e))?;
+    Ok(true)
+}
+
+// zip 解压或部分打包工具不保留权限位，后端主程序与 _internal 下的原生二进制可能没有执行位，spawn 时报 Permission denied；
+// 校验阶段检测并统一补齐，返回修复的文件数。仅检查无执行位、且扩展名像二进制的文件，避免逐个读取大量 .pyc 等数据文件
+#[cfg(unix)]
+fn ensure_backend_exec_permissions(backend_executable: &std::path::Path) -> Result<usize, String> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut fixed = 0usize;
+    if repair_exec_bit(backend_executable)? {
+        fixed += 1;
+    }
+    let internal_dir = match backend_executable.parent() {
+        Some(d) => d.join("_internal"),
+        None => return Ok(fixed),
+    };
+    let mut stack = vec![internal_dir];
+    while let Some(dir) = stack.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let ft = match entry.file_type() {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+            let path = entry.path();
+            if ft.is_dir() {
+                stack.push(path);
+                continue;
             }
-            Err(e) => {
-                append_log_line(
-                    early_log_path.clone(),
-                    &format!("[meta] ensure_backend_executable_available_error={}", e),
-                );
-                return Err(e);
+            if !ft.is_file() {
+                continue;
+            }
+            let has_exec = entry
+                .metadata()
+                .map(|m| m.permissions().mode() & 0o111 != 0)
+                .unwrap_or(true);
+            if has_exec {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            let looks_binary = !name.contains('.')
+                || name.ends_with(".so")
+                || name.contains(".so.")
+                || name.ends_with(".dylib");
+            if looks_binary && is_native_binary(&path) && repair_exec_bit(&path)? {
+                fixed += 1;
             }
         }
-    } else {
-        None
-    };
+    }
+    Ok(fixed)
+}
 
-    #[cfg(target_os = "windows")]
-    {
-        if is_dev_mode {
-            if let Err(e) = ensure_ffmpeg_binaries(&resource_root).await {
-                eprintln!("开发模式自动准备FFmpeg失败: {}", e);
-            }
+#[cfg(not(unix))]
+fn ensure_backend_exec_permissions(_backend_executable: &std::path::Path) -> Result<usize, String> {
+    Ok(0)
+}
+
+const BACKEND_EXE_CACHE_FILE: &str = "backend_executable_path.txt";
+
+// 可执行文件路径缓存允许所在的目录：当前安装的资源根目录、主程序目录，以及（Windows）应用数据目录下的解压目录
+fn backend_exe_cache_roots(
+    app_handle: &AppHandle,
+    resource_root: &std::path::Path,
+    exe_dir_fallback: Option<&std::path::Path>,
+) -> Vec<PathBuf> {
+    let mut roots = vec![resource_root.to_path_buf()];
+    if let Some(dir) = exe_dir_fallback {
+        roots.push(dir.to_path_buf());
+    }
+    if cfg!(target_os = "windows") {
+        if let Ok(app_data_dir) = app_handle.path().app_data_dir() {
+            roots.push(app_data_dir.join("superAutoCutVideoBackend"));
         }
     }
+    roots
+}
+
+// 读取可执行文件路径缓存：首行为写入时的资源根目录，次行为可执行文件路径。
+// 缓存位于多份安装共享的 app_cache_dir，仅当记录的资源根目录与当前一致、且路径位于当前安装目录下时才复用
+fn read_backend_exe_cache(
+    app_handle: &AppHandle,
+    resource_root: &std::path::Path,
+    exe_dir_fallback: Option<&std::path::Path>,
+) -> Option<PathBuf> {
+    let cache_path = app_handle
+        .path()
+        .app_cache_dir()
+        .ok()?
+        .join(BACKEND_EXE_CACHE_FILE);
+    let content = std::fs::read_to_string(cache_path).ok()?;
+    let mut lines = content.lines().map(|l| l.trim());
+    let recorded_root = lines.next()?;
+    let exe = PathBuf::from(lines.next()?);
+    if recorded_root != resource_root.to_string_lossy() || exe.as_os_str().is_empty() {
+        return None;
+    }
+    let roots = backend_exe_cache_roots(app_handle, resource_root, exe_dir_fallback);
+    if !roots.iter().any(|r| exe.starts_with(r)) {
+        return None;
+    }
+    Some(exe)
+}
+
+fn write_backend_exe_cache(
+    app_handle: &AppHandle,
+    resource_root: &std::path::Path,
+    exe: &std::path::Path,
+) {
+    if let Ok(dir) = app_handle.path().app_cache_dir() {
+        let _ = std::fs::create_dir_all(&dir);
+        let _ = std::fs::write(
+            dir.join(BACKEND_EXE_CACHE_FILE),
+            format!(
+                "{}\n{}\n",
+                resource_root.to_string_lossy(),
+                exe.to_string_lossy()
+            ),
+        );
+    }
+}
 
-    // 尝试定位打包的后端可执行文件
+// 枚举打包后端可执行文件的候选路径（首项为默认路径）
+fn collect_backend_candidates(
+    resource_root: &std::path::Path,
+    exe_dir_fallback: Option<&std::path::Path>,
+) -> Vec<PathBuf> {
     let primary_path_dir = if cfg!(target_os = "windows") {
         resource_root
             .join("superAutoCutVideoBackend")
@@ -780,14 +2623,23 @@ async fn start_backend(
     } else {
         resource_root.join("superAutoCutVideoBackend")
     };
-    let mut candidates: Vec<PathBuf> = Vec::new();
-    #[cfg(target_os = "windows")]
-    if let Some(p) = extracted_backend_exe.clone() {
-        candidates.push(p);
+    let mut candidates: Vec<PathBuf> = vec![primary_path_dir, primary_path_file];
+    // macOS .app：可执行文件位于 Foo.app/Contents/MacOS，资源位于同级的 Contents/Resources
+    #[cfg(target_os = "macos")]
+    if let Some(contents_dir) = exe_dir_fallback
+        .and_then(|d| d.parent())
+        .filter(|p| p.file_name().map_or(false, |n| n == "Contents"))
+    {
+        let mac_resources = contents_dir.join("Resources");
+        for base in [mac_resources.join("resources"), mac_resources] {
+            candidates.push(
+                base.join("superAutoCutVideoBackend")
+                    .join("superAutoCutVideoBackend"),
+            );
+            candidates.push(base.join("superAutoCutVideoBackend"));
+        }
     }
-    candidates.push(primary_path_dir.clone());
-    candidates.push(primary_path_file.clone());
-    if let Some(dir) = &exe_dir_fallback {
+    if let Some(dir) = exe_dir_fallback {
         if cfg!(target_os = "windows") {
             candidates.push(dir.join("resources").join("superAutoCutVideoBackend.exe"));
             candidates.push(
@@ -843,359 +2695,3486 @@ async fn start_backend(
             }
         }
     }
-    let backend_executable = candidates
-        .into_iter()
-        .find(|p| p.exists())
-        .unwrap_or(primary_path_dir.clone());
-    append_log_line(
-        early_log_path.clone(),
-        &format!(
-            "[meta] backend_executable_candidate={} exists={}",
-            backend_executable.to_string_lossy(),
-            backend_executable.exists()
-        ),
-    );
+    candidates
+}
 
-    #[cfg(target_os = "windows")]
-    {
-        if !backend_executable.exists() && !is_dev_mode {
-            let _ = ensure_ffmpeg_binaries(&resource_root).await.map_err(|e| {
-                eprintln!("自动准备FFmpeg失败: {}", &e);
-                e
-            });
+// 在资源目录、可执行文件与当前工作目录的祖先目录中查找源码后端 backend/main.py
+fn find_backend_script(resource_dir: &std::path::Path) -> Option<PathBuf> {
+    let mut search_roots: Vec<PathBuf> = vec![resource_dir.to_path_buf()];
+    if let Ok(exe) = std::env::current_exe() {
+        search_roots.push(exe);
+    }
+    if let Ok(cwd) = std::env::current_dir() {
+        search_roots.push(cwd);
+    }
+    for root in search_roots {
+        for anc in root.ancestors().take(8) {
+            let cand = anc.join("backend").join("main.py");
+            if cand.exists() {
+                return Some(cand);
+            }
         }
     }
+    None
+}
 
-    let mut cmd = if !prefer_python_backend && backend_executable.exists() {
-        // 使用打包的可执行文件
-        append_log_line(early_log_path.clone(), "[meta] use_packaged_backend_exe=1");
-        println!("使用打包的后端可执行文件: {:?}", backend_executable);
-        let backend_working_dir = backend_executable
-            .parent()
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| resource_root.clone());
-        let mut c = apply_windows_no_window(Command::new(&backend_executable));
-        c.current_dir(backend_working_dir);
-        c
-    } else if is_dev_mode {
-        let mut backend_script: Option<PathBuf> = None;
-        let mut search_roots: Vec<PathBuf> = vec![resource_dir.clone()];
-        if let Ok(exe) = std::env::current_exe() {
-            search_roots.push(exe);
-        }
-        if let Ok(cwd) = std::env::current_dir() {
-            search_roots.push(cwd);
-        }
-        for root in search_roots {
-            for anc in root.ancestors().take(8) {
-                let cand = anc.join("backend").join("main.py");
-                if cand.exists() {
-                    backend_script = Some(cand);
-                    break;
-                }
-            }
-            if backend_script.is_some() {
-                break;
-            }
+// Windows 环境变量值上限为 32767 字符，超过时 spawn 可能失败
+const MAX_BACKEND_PATH_LEN: usize = 32767;
+
+// 按顺序拼接 PATH 条目：去掉空项与重复项（Windows 下不区分大小写、忽略末尾分隔符）
+fn join_path_entries(entries: &[String], sep: &str) -> String {
+    let mut seen = std::collections::HashSet::new();
+    let mut out: Vec<&str> = Vec::new();
+    for entry in entries {
+        let trimmed = entry.trim();
+        if trimmed.is_empty() {
+            continue;
         }
-        let backend_script =
-            backend_script.ok_or_else(|| "后端脚本不存在: backend/main.py".to_string())?;
-        if !backend_script.exists() {
-            return Err(format!("后端脚本不存在: {:?}", backend_script));
+        let mut key = trimmed.trim_end_matches(['/', '\\']).to_string();
+        if key.is_empty() {
+            key = trimmed.to_string();
         }
-        append_log_line(
-            early_log_path.clone(),
-            &format!(
-                "[meta] use_python_backend_script={} ",
-                backend_script.to_string_lossy()
-            ),
-        );
-        println!("使用Python运行后端脚本: {:?}", backend_script);
-        let backend_dir = backend_script.parent().unwrap().to_path_buf();
-        let venv_py_unix = backend_dir.join(".venv").join("bin").join("python3");
-        let venv_py_unix_alt = backend_dir.join(".venv").join("bin").join("python");
-        let venv_py_win = backend_dir.join(".venv").join("Scripts").join("python.exe");
-        let env_override = std::env::var("BACKEND_PYTHON").ok();
-        let python_cmd: String = if let Some(p) = env_override {
-            p
-        } else if venv_py_unix.exists() {
-            venv_py_unix.to_string_lossy().to_string()
-        } else if venv_py_unix_alt.exists() {
-            venv_py_unix_alt.to_string_lossy().to_string()
-        } else if venv_py_win.exists() {
-            venv_py_win.to_string_lossy().to_string()
-        } else if which::which("python3").is_ok() {
-            "python3".to_string()
-        } else {
-            "python".to_string()
-        };
-        append_log_line(
-            early_log_path.clone(),
-            &format!("[meta] python_cmd={}", python_cmd),
-        );
-        println!("选择的 Python 解释器: {}", python_cmd);
-        let mut c = Command::new(python_cmd);
-        c.arg(backend_script);
-        #[cfg(target_os = "windows")]
-        {
-            c.creation_flags(CREATE_NO_WINDOW);
+        if cfg!(target_os = "windows") {
+            key = key.to_lowercase();
         }
-        c.current_dir(backend_dir);
-        c
+        if seen.insert(key) {
+            out.push(trimmed);
+        }
+    }
+    out.join(sep)
+}
+
+// 为源码后端选择 Python 解释器：BACKEND_PYTHON > backend/.venv > 系统 python3/python
+fn select_python_cmd(backend_dir: &std::path::Path) -> String {
+    let venv_py_unix = backend_dir.join(".venv").join("bin").join("python3");
+    let venv_py_unix_alt = backend_dir.join(".venv").join("bin").join("python");
+    let venv_py_win = backend_dir.join(".venv").join("Scripts").join("python.exe");
+    if let Ok(p) = std::env::var("BACKEND_PYTHON") {
+        p
+    } else if venv_py_unix.exists() {
+        venv_py_unix.to_string_lossy().to_string()
+    } else if venv_py_unix_alt.exists() {
+        venv_py_unix_alt.to_string_lossy().to_string()
+    } else if venv_py_win.exists() {
+        venv_py_win.to_string_lossy().to_string()
+    } else if which::which("python3").is_ok() {
+        "python3".to_string()
     } else {
-        let err = "未找到打包的后端可执行文件，请检查打包配置 bundle.resources".to_string();
-        let path = std::env::temp_dir().join("super_auto_cut_backend.log");
-        append_log_line(path, &format!("[error] {}", err));
-        return Err(err);
-    };
+        "python".to_string()
+    }
+}
 
-    // 设置环境变量
-    let port_env = std::env::var("SACV_FORCE_PORT")
-        .ok()
-        .and_then(|s| s.parse::<u16>().ok())
-        .filter(|p| *p > 0);
-    let port: u16 = port_env.unwrap_or_else(|| choose_backend_port(is_dev_mode));
-    let boot_token = generate_boot_token();
-    let orig_path = std::env::var("PATH").unwrap_or_default();
-    let sep = if cfg!(target_os = "windows") {
-        ";"
-    } else {
-        ":"
-    };
-    let backend_dir = backend_executable
-        .parent()
-        .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_else(|| resource_root.to_string_lossy().to_string());
-    let resource_dir_s = resource_root.to_string_lossy().to_string();
-    let internal_dir_s = backend_executable
+// 源码后端要求的最低 Python 版本
+const MIN_PYTHON_VERSION: (u32, u32) = (3, 9);
+
+// 执行 `python --version` 校验解释器版本（Python 2 把版本输出到 stderr，故合并两路输出解析）
+fn check_python_version(python_cmd: &str) -> Result<(u32, u32), String> {
+    let mut cmd = Command::new(python_cmd);
+    cmd.arg("--version").stdin(Stdio::null());
+    let output = apply_windows_no_window(cmd)
+        .output()
+        .map_err(|e| format!("无法执行 Python 解释器 {}: {}", python_cmd, e))?;
+    let text = format!(
+        "{} {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let version = text
+        .split_whitespace()
+        .skip_while(|w| *w != "Python")
+        .nth(1)
+        .ok_or_else(|| format!("无法识别 Python 版本（{}）: {}", python_cmd, text.trim()))?;
+    let mut parts = version.split('.').map(|p| p.parse::<u32>().unwrap_or(0));
+    let major = parts.next().unwrap_or(0);
+    let minor = parts.next().unwrap_or(0);
+    if (major, minor) < MIN_PYTHON_VERSION {
+        return Err(format!(
+            "Python 版本过低: {} 为 {}，需要 >= {}.{}",
+            python_cmd, version, MIN_PYTHON_VERSION.0, MIN_PYTHON_VERSION.1
+        ));
+    }
+    Ok((major, minor))
+}
+
+// 构造以 Python 运行 backend/main.py 的启动命令（含解释器版本校验，BACKEND_PYTHON 显式指定时同样校验）
+fn build_python_backend_command(
+    backend_script: &std::path::Path,
+    trace: &Mutex<HashMap<String, String>>,
+) -> Result<Command, String> {
+    let backend_dir = backend_script
         .parent()
-        .map(|p| p.join("_internal"))
-        .filter(|p| p.exists())
-        .map(|p| p.to_string_lossy().to_string());
-    let new_path = match internal_dir_s {
-        Some(internal) => {
-            if backend_dir != resource_dir_s {
-                format!("{}{}{}{}{}{}{}", backend_dir, sep, resource_dir_s, sep, internal, sep, orig_path)
-            } else {
-                format!("{}{}{}{}{}", resource_dir_s, sep, internal, sep, orig_path)
-            }
-        }
-        None => {
-            if backend_dir != resource_dir_s {
-                format!("{}{}{}{}{}", backend_dir, sep, resource_dir_s, sep, orig_path)
-            } else {
-                format!("{}{}{}", resource_dir_s, sep, orig_path)
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."));
+    let python_cmd = select_python_cmd(&backend_dir);
+    log_meta(trace, "python_cmd", &python_cmd);
+    app_log!(info, "选择的 Python 解释器: {}", python_cmd);
+    let (major, minor) = check_python_version(&python_cmd).map_err(|e| {
+        log_meta(trace, "python_version_error", &e);
+        e
+    })?;
+    log_meta(trace, "python_version", format!("{}.{}", major, minor));
+    let mut c = apply_windows_no_window(Command::new(python_cmd));
+    c.arg(backend_script);
+    c.current_dir(backend_dir);
+    Ok(c)
+}
+
+// 额外前置到后端 PATH 的目录：环境变量 SACV_EXTRA_PATH（按系统 PATH 分隔符分隔）优先，否则取配置 extra_path（字符串数组）；
+// 不存在的目录跳过
+fn backend_extra_path_dirs(app_handle: &AppHandle) -> Vec<String> {
+    let dirs: Vec<PathBuf> = match std::env::var_os("SACV_EXTRA_PATH").filter(|v| !v.is_empty()) {
+        Some(raw) => std::env::split_paths(&raw).collect(),
+        None => load_app_config(app_handle)
+            .get("extra_path")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|d| d.as_str())
+                    .map(PathBuf::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    dirs.into_iter()
+        .filter(|d| !d.as_os_str().is_empty())
+        .filter(|d| {
+            let ok = d.is_dir();
+            if !ok {
+                append_log_line(
+                    backend_log_path(),
+                    &format!(
+                        "[warn] 额外 PATH 目录不存在，已跳过: {}",
+                        d.to_string_lossy()
+                    ),
+                );
             }
+            ok
+        })
+        .map(|d| d.to_string_lossy().to_string())
+        .collect()
+}
+
+// 后端额外命令行参数：环境变量 SACV_BACKEND_ARGS（按空白分隔）优先，否则取配置 backend_args（字符串数组）
+fn backend_extra_args(app_handle: &AppHandle) -> Vec<String> {
+    if let Ok(raw) = std::env::var("SACV_BACKEND_ARGS") {
+        if !raw.trim().is_empty() {
+            return raw.split_whitespace().map(|s| s.to_string()).collect();
         }
-    };
-    let backend_tmp_dir = std::env::var("SACV_BACKEND_TMPDIR")
-        .ok()
-        .map(PathBuf::from)
-        .or_else(|| app_handle.path().app_cache_dir().ok())
-        .unwrap_or_else(std::env::temp_dir)
-        .join("super_auto_cut_backend_tmp");
-    let _ = std::fs::create_dir_all(&backend_tmp_dir);
-    let backend_tmp_dir_s = backend_tmp_dir.to_string_lossy().to_string();
-    *state.backend_port.lock().unwrap() = port;
-    *state.backend_boot_token.lock().unwrap() = Some(boot_token.clone());
-    cmd.env("HOST", host)
-        .env("PORT", port.to_string())
-        .env("PATH", new_path)
-        .env("TEMP", backend_tmp_dir_s.clone())
-        .env("TMP", backend_tmp_dir_s)
-        .env("SACV_BOOT_TOKEN", boot_token.clone())
-        .env("SACV_RUNTIME", "tauri")
-        .env(
-            "SACV_INSTALL_DIR",
-            install_dir
-                .as_ref()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_default(),
-        )
-        .stdin(Stdio::null())
+    }
+    load_app_config(app_handle)
+        .get("backend_args")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|a| a.as_str())
+                .filter(|a| !a.is_empty())
+                .map(|a| a.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 指定 profile 的额外环境变量：取配置 backend_profiles.<profile>.env（如模型路径/设备），值非字符串时转为 JSON 文本
+fn backend_profile_env(app_handle: &AppHandle, profile: &str) -> Vec<(String, String)> {
+    load_app_config(app_handle)
+        .get("backend_profiles")
+        .and_then(|v| v.get(profile))
+        .and_then(|v| v.get("env"))
+        .and_then(|v| v.as_object())
+        .map(|obj| {
+            obj.iter()
+                .map(|(k, v)| {
+                    let v = v
+                        .as_str()
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| v.to_string());
+                    (k.clone(), v)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 把后端启动环境变量与标准输入输出配置应用到命令上
+fn apply_backend_launch_env(cmd: &mut Command, launch_env: &[(String, String)]) {
+    for (k, v) in launch_env {
+        cmd.env(k, v);
+    }
+    cmd.stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
-
-    // 启动进程
-    match cmd.spawn() {
-        Ok(mut child) => {
-            println!(
-                "[backend] 已启动进程，等待就绪：http://{}:{} (pid={})",
-                host,
-                port,
-                child.id()
-            );
-            // 捕获日志到临时文件
-            let log_path = std::env::temp_dir().join("super_auto_cut_backend.log");
-            let _ = std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(&log_path);
-            if let Some(stdout) = child.stdout.take() {
-                let path_clone = log_path.clone();
-                thread::spawn(move || {
-                    let reader = BufReader::new(stdout);
-                    for line in reader.lines() {
-                        if let Ok(l) = line {
-                            append_log_line(path_clone.clone(), &format!("[stdout] {}", l));
-                        }
-                    }
-                });
-            }
-            if let Some(stderr) = child.stderr.take() {
-                let path_clone = log_path.clone();
-                thread::spawn(move || {
-                    let reader = BufReader::new(stderr);
-                    for line in reader.lines() {
-                        if let Ok(l) = line {
-                            append_log_line(path_clone.clone(), &format!("[stderr] {}", l));
-                        }
-                    }
-                });
+    // Unix：让后端成为新 session/进程组组长，停止时可整组结束（含 PyInstaller fork 出的孙进程）
+    #[cfg(unix)]
+    unsafe {
+        use std::os::unix::process::CommandExt;
+        cmd.pre_exec(|| {
+            if setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
             }
+            Ok(())
+        });
+    }
+}
 
-            let pid = child.id();
-            {
-                let mut process_guard = state.backend_process.lock().unwrap();
-                *process_guard = Some(child);
-            }
-            state.backend_starting.store(false, Ordering::SeqCst);
+// 强制端口：运行中通过 set_backend_port 指定的优先，其次为环境变量 SACV_FORCE_PORT
+fn forced_backend_port(backend: &BackendInstance) -> Option<u16> {
+    if let Some(p) = *backend.port_override.lock().unwrap() {
+        return Some(p);
+    }
+    std::env::var("SACV_FORCE_PORT")
+        .ok()
+        .and_then(|s| s.parse::<u16>().ok())
+        .filter(|p| *p > 0)
+}
 
-            // 等待后端就绪（最多 60 秒，避免首次解压或冷启动偏慢）
-            if wait_for_backend_ready(host, port, 60).await {
-                println!("[backend] 已就绪：http://{}:{}", host, port);
-                let _ = tauri_plugin_notification::NotificationExt::notification(&app_handle)
-                    .builder()
-                    .title("AI智能视频剪辑")
-                    .body("后端服务启动成功")
-                    .show();
+// 判断目录是否为后端资源根目录的标志文件/目录（打包 zip、已解压目录、源码后端目录）
+const BACKEND_RESOURCE_MARKERS: &[&str] = &[
+    "superAutoCutVideoBackend.zip",
+    "superAutoCutVideoBackend",
+    "backend",
+];
 
-                Ok(BackendStatus {
-                    running: true,
-                    port,
-                    pid: Some(pid),
-                    boot_token: Some(boot_token),
-                })
+// resource_root 的选择结果：checked 为按序检查过的路径及其状态，reason 说明选中 root 的原因
+struct ResourceRootDecision {
+    root: PathBuf,
+    checked: Vec<String>,
+    reason: String,
+}
+
+impl ResourceRootDecision {
+    fn describe(&self) -> String {
+        format!(
+            "checked=[{}] chosen={} reason={}",
+            self.checked.join("; "),
+            self.root.to_string_lossy(),
+            self.reason
+        )
+    }
+}
+
+// 依次检查 resource_dir/resources 与 resource_dir，选第一个包含后端资源标志的目录；
+// 都不包含时沿用旧规则（resources 子目录存在则用它，否则用 resource_dir）
+fn resolve_resource_root(resource_dir: &std::path::Path) -> ResourceRootDecision {
+    let mut checked = Vec::new();
+    let mut first_existing: Option<PathBuf> = None;
+    for candidate in [resource_dir.join("resources"), resource_dir.to_path_buf()] {
+        let exists = candidate.is_dir();
+        let markers: Vec<&str> = BACKEND_RESOURCE_MARKERS
+            .iter()
+            .copied()
+            .filter(|m| exists && candidate.join(m).exists())
+            .collect();
+        checked.push(format!(
+            "{} (exists={}, markers={})",
+            candidate.to_string_lossy(),
+            exists,
+            if markers.is_empty() {
+                "-".to_string()
             } else {
-                // 超时未就绪，尝试从日志解析实际监听端口
-                if let Some(found_port) = parse_backend_port_from_log() {
-                    *state.backend_port.lock().unwrap() = found_port;
-                    println!(
-                        "[backend] 从日志解析到监听端口：http://{}:{}",
-                        host, found_port
-                    );
-                    Ok(BackendStatus {
-                        running: true,
-                        port: found_port,
-                        pid: Some(pid),
-                        boot_token: state.backend_boot_token.lock().unwrap().clone(),
-                    })
-                } else {
-                    if let Some((found_port, found_token)) =
-                        discover_existing_backend_quick(host, !is_dev_mode).await
-                    {
-                        *state.backend_port.lock().unwrap() = found_port;
-                        *state.backend_boot_token.lock().unwrap() = found_token.clone();
-                        println!(
-                            "[backend] 已发现运行中的后端：http://{}:{}",
-                            host, found_port
-                        );
-                        Ok(BackendStatus {
-                            running: true,
-                            port: found_port,
-                            pid: Some(pid),
-                            boot_token: found_token,
-                        })
-                    } else {
-                        // 未发现已就绪端口，保留已启动的进程，返回错误以提示检查日志，但不杀进程
-                        Err("后端服务启动超时，但进程已保留；请查看临时日志 super_auto_cut_backend.log".to_string())
-                    }
-                }
+                markers.join(",")
             }
+        ));
+        if !markers.is_empty() {
+            return ResourceRootDecision {
+                root: candidate,
+                checked,
+                reason: format!("包含后端资源 {}", markers.join(",")),
+            };
+        }
+        if exists && first_existing.is_none() {
+            first_existing = Some(candidate);
         }
+    }
+    let reason = match &first_existing {
+        Some(_) => "未找到后端资源标志，选第一个存在的目录".to_string(),
+        None => "候选目录均不存在，回退为 resource_dir".to_string(),
+    };
+    ResourceRootDecision {
+        root: first_existing.unwrap_or_else(|| resource_dir.to_path_buf()),
+        checked,
+        reason,
+    }
+}
+
+// 获取资源目录路径（Tauri 资源目录取不到时回退到与应用同级的 resources 目录）及 resource_root 的选择决策
+fn backend_resource_decision(app_handle: &AppHandle) -> (PathBuf, ResourceRootDecision) {
+    let resource_dir = match app_handle.path().resource_dir() {
+        Ok(p) => p,
         Err(e) => {
-            state.backend_starting.store(false, Ordering::SeqCst);
-            *state.backend_port.lock().unwrap() = 0;
-            *state.backend_boot_token.lock().unwrap() = None;
-            let path = std::env::temp_dir().join("super_auto_cut_backend.log");
+            let exe_dir_fallback = std::env::current_exe()
+                .ok()
+                .and_then(|p| p.parent().map(|d| d.to_path_buf()));
+            let dir = if let Some(dir) = exe_dir_fallback {
+                dir.join("resources")
+            } else {
+                std::env::current_dir()
+                    .unwrap_or_else(|_| std::path::PathBuf::from("."))
+                    .join("resources")
+            };
             append_log_line(
-                path,
-                &format!("[error] spawn_failed: {}", e),
+                backend_log_path(),
+                &format!(
+                    "[warn] 无法获取资源目录（{}），回退为 {}",
+                    e,
+                    dir.to_string_lossy()
+                ),
             );
-            Err(format!("启动后端失败: {}", e))
+            dir
         }
+    };
+    let decision = resolve_resource_root(&resource_dir);
+    (resource_dir, decision)
+}
+
+// 获取资源目录路径；第二项为实际存放资源的根目录
+fn backend_resource_dirs(app_handle: &AppHandle) -> (PathBuf, PathBuf) {
+    let (resource_dir, decision) = backend_resource_decision(app_handle);
+    (resource_dir, decision.root)
+}
+
+const BACKEND_SOURCE_MODES: [&str; 3] = ["auto", "python", "packaged"];
+
+// 后端来源覆盖，返回 (强制源码后端, 强制打包后端)：环境变量 FORCE_PYTHON_BACKEND=1 / FORCE_PACKAGED_BACKEND=1 优先，
+// 都未设置时取配置 backend_source（auto 为按开发/打包环境自动判断）
+fn backend_source_overrides(app_handle: &AppHandle) -> (bool, bool) {
+    let env_python = std::env::var("FORCE_PYTHON_BACKEND").ok().as_deref() == Some("1");
+    let env_packaged = std::env::var("FORCE_PACKAGED_BACKEND").ok().as_deref() == Some("1");
+    if env_python || env_packaged {
+        return (env_python, env_packaged);
+    }
+    match load_app_config(app_handle)
+        .get("backend_source")
+        .and_then(|v| v.as_str())
+    {
+        Some("python") => (true, false),
+        Some("packaged") => (false, true),
+        _ => (false, false),
     }
 }
 
-// Tauri命令：停止Python后端
+// Tauri命令：设置后端来源（auto/python/packaged），下次启动或 restart_backend 时生效
 #[tauri::command]
-async fn stop_backend(state: State<'_, AppState>) -> Result<bool, String> {
-    let mut process_guard = state.backend_process.lock().unwrap();
+async fn set_backend_source(app_handle: AppHandle, mode: String) -> Result<(), String> {
+    let mode = mode.trim().to_lowercase();
+    if !BACKEND_SOURCE_MODES.contains(&mode.as_str()) {
+        return Err(format!(
+            "不支持的后端来源: {}（可选 auto/python/packaged）",
+            mode
+        ));
+    }
+    update_app_config(&app_handle, |config| {
+        config.insert("backend_source".to_string(), serde_json::json!(mode));
+    })
+}
 
-    if let Some(mut child) = process_guard.take() {
-        let pid = child.id();
-        match child.kill() {
-            Ok(_) => {
-                let _ = child.wait(); // 等待进程完全退出
-                *state.backend_port.lock().unwrap() = 0;
-                *state.backend_boot_token.lock().unwrap() = None;
-                println!("[backend] 已停止 (pid={})", pid);
-                #[cfg(target_os = "windows")]
-                {
-                    // 额外兜底：强制结束所有同名后端进程，避免残留
-                    kill_all_backend_processes();
+// 后端启动计划：后端来源、可执行文件/脚本与端口的决策。start_backend 与 probe_backend_launch 共用同一份决策，
+// dry_run 时只做定位与检查（不解压、不下载、不写缓存），保证预检结果与实际启动一致
+struct LaunchPlan {
+    resource_dir: PathBuf,
+    resource_root: PathBuf,
+    resource_decision: ResourceRootDecision,
+    force_packaged_backend: bool,
+    backend_executable: PathBuf,
+    // 使用打包可执行文件；否则 script 为 Some 时用 Python 运行源码后端，二者皆无时 launch_error 说明原因
+    use_packaged_backend: bool,
+    script: Option<PathBuf>,
+    launch_error: Option<String>,
+    port: u16,
+    port_forced: bool,
+    notes: Vec<String>,
+}
+
+async fn plan_backend_launch(
+    app_handle: &AppHandle,
+    state: &AppState,
+    backend: &BackendInstance,
+    trace: &Mutex<HashMap<String, String>>,
+    dry_run: bool,
+) -> Result<LaunchPlan, BackendError> {
+    let is_dev_mode =
+        cfg!(debug_assertions) || std::env::var("TAURI_DEV").ok().as_deref() == Some("1");
+    let (resource_dir, resource_decision) = backend_resource_decision(app_handle);
+    let resource_root = resource_decision.root.clone();
+    let exe_dir_fallback = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|d| d.to_path_buf()));
+    let mut notes: Vec<String> = Vec::new();
+
+    // 后端来源：环境变量 FORCE_PYTHON_BACKEND/FORCE_PACKAGED_BACKEND 优先，其次应用内设置 backend_source
+    let (force_python_backend, force_packaged_backend) = backend_source_overrides(app_handle);
+    let backend_zip_exists = resource_root.join("superAutoCutVideoBackend.zip").exists();
+    let backend_folder_exists = resource_root
+        .join("superAutoCutVideoBackend")
+        .join("superAutoCutVideoBackend.exe")
+        .exists();
+    // 强制源码后端：任何模式下优先使用 backend/main.py，找不到脚本时降级
+    let forced_backend_script = if force_python_backend {
+        find_backend_script(&resource_dir)
+    } else {
+        None
+    };
+    if force_python_backend && forced_backend_script.is_none() {
+        log_meta(
+            trace,
+            "force_python_backend_fallback",
+            "backend/main.py not found",
+        );
+        notes.push("已强制使用源码后端，但未找到 backend/main.py，将按默认规则选择".to_string());
+    }
+    let prefer_python_backend = forced_backend_script.is_some()
+        || (is_dev_mode
+            && !force_packaged_backend
+            && !backend_zip_exists
+            && !backend_folder_exists);
+
+    log_meta(trace, "is_dev_mode", is_dev_mode);
+    log_meta(trace, "force_python_backend", force_python_backend);
+    log_meta(trace, "force_packaged_backend", force_packaged_backend);
+    log_meta(trace, "prefer_python_backend", prefer_python_backend);
+    log_meta(trace, "resource_dir", resource_dir.to_string_lossy());
+    log_meta(trace, "resource_root", resource_root.to_string_lossy());
+    log_meta(
+        trace,
+        "resource_root_decision",
+        resource_decision.describe(),
+    );
+    log_meta(trace, "backend_zip_exists", backend_zip_exists);
+
+    if !dry_run {
+        *backend.start_stage.lock().unwrap() = "prepare_resources";
+    }
+    // 后端包解压与（开发模式）FFmpeg 准备互不依赖，并行执行以缩短首次冷启动；二者各自记录日志键，错误分别汇报
+    #[cfg(not(target_os = "windows"))]
+    let _ = state;
+    #[cfg(target_os = "windows")]
+    let needs_extract = !prefer_python_backend && backend_zip_exists && !backend_folder_exists;
+    // 预检不解压，只推算解压后的路径
+    #[cfg(target_os = "windows")]
+    let extracted_backend_exe = if dry_run {
+        match app_handle.path().app_data_dir() {
+            Ok(app_data_dir) if needs_extract => {
+                let extracted = app_data_dir
+                    .join("superAutoCutVideoBackend")
+                    .join("superAutoCutVideoBackend.exe");
+                if !extracted.is_file() {
+                    notes.push("后端压缩包尚未解压，实际启动时会先解压到应用数据目录".to_string());
                 }
-                Ok(true)
+                Some(extracted)
             }
-            Err(e) => Err(format!("停止后端失败: {}", e)),
+            _ => None,
+        }
+    } else {
+        let extract_fut = async {
+            if !needs_extract {
+                return Ok(None);
+            }
+            let zip_path = resource_root.join("superAutoCutVideoBackend.zip");
+            if let Ok(app_data_dir) = app_handle.path().app_data_dir() {
+                log_meta(trace, "app_data_dir", app_data_dir.to_string_lossy());
+            }
+            log_meta(trace, "backend_zip_path", zip_path.to_string_lossy());
+            log_meta(trace, "ensure_backend_executable_available", "begin");
+            let app_clone = app_handle.clone();
+            let root_clone = resource_root.clone();
+            let result = tauri::async_runtime::spawn_blocking(move || {
+                ensure_backend_executable_available(&app_clone, &root_clone)
+            })
+            .await
+            .map_err(|e| format!("解压后端任务异常: {}", e))
+            .and_then(|r| r);
+            match result {
+                Ok(p) => {
+                    log_meta(trace, "ensure_backend_executable_available", "ok");
+                    log_meta(trace, "extracted_backend_exe", p.to_string_lossy());
+                    log_meta(trace, "extracted_backend_exe_exists", p.exists());
+                    Ok(Some(p))
+                }
+                Err(e) => {
+                    log_meta(trace, "ensure_backend_executable_available", "error");
+                    log_meta(trace, "ensure_backend_executable_available_error", &e);
+                    Err(e)
+                }
+            }
+        };
+        let ffmpeg_fut = async {
+            if is_dev_mode {
+                ensure_ffmpeg_binaries(
+                    &resource_root,
+                    download_proxy_url(app_handle),
+                    &state.progress_tasks,
+                )
+                .await
+            } else {
+                Ok(())
+            }
+        };
+        let (extract_result, ffmpeg_result) = tokio::join!(extract_fut, ffmpeg_fut);
+        if let Err(e) = &ffmpeg_result {
+            app_log!(error, "开发模式自动准备FFmpeg失败: {}", e);
+            log_meta(trace, "ensure_ffmpeg_binaries_error", e);
         }
+        match extract_result {
+            Ok(p) => p,
+            Err(e) => {
+                return Err(BackendError::ExtractFailed {
+                    message: match ffmpeg_result {
+                        Err(fe) => format!("{}；另外 FFmpeg 准备也失败: {}", e, fe),
+                        Ok(()) => e,
+                    },
+                });
+            }
+        }
+    };
+
+    // 尝试定位打包的后端可执行文件：优先复用上次命中的缓存路径，失效时再完整枚举候选
+    let cached_backend_executable =
+        read_backend_exe_cache(app_handle, &resource_root, exe_dir_fallback.as_deref())
+            .filter(|p| p.is_file());
+    #[cfg(target_os = "windows")]
+    let cached_backend_executable = cached_backend_executable
+        .filter(|c| extracted_backend_exe.as_ref().map_or(true, |e| e == c));
+    let backend_executable = if let Some(p) = cached_backend_executable {
+        log_meta(trace, "backend_executable_cache", "hit");
+        p
     } else {
+        let mut candidates: Vec<PathBuf> = Vec::new();
         #[cfg(target_os = "windows")]
+        if let Some(p) = extracted_backend_exe.clone() {
+            candidates.push(p);
+        }
+        let enumerated = collect_backend_candidates(&resource_root, exe_dir_fallback.as_deref());
+        let default_path = enumerated[0].clone();
+        candidates.extend(enumerated);
+        // Unix 下后端目录与可执行文件同名，需用 is_file 避免把目录误判为可执行文件
+        let found = candidates.into_iter().find(|p| p.is_file());
+        log_meta(trace, "backend_executable_cache", "miss");
+        if let (Some(p), false) = (&found, dry_run) {
+            write_backend_exe_cache(app_handle, &resource_root, p);
+        }
+        found.unwrap_or(default_path)
+    };
+    log_meta(
+        trace,
+        "backend_executable_candidate",
+        backend_executable.to_string_lossy(),
+    );
+    log_meta(
+        trace,
+        "backend_executable_exists",
+        backend_executable.exists(),
+    );
+
+    #[cfg(target_os = "windows")]
+    if !dry_run && !backend_executable.exists() && !is_dev_mode {
+        if let Err(e) = ensure_ffmpeg_binaries(
+            &resource_root,
+            download_proxy_url(app_handle),
+            &state.progress_tasks,
+        )
+        .await
         {
-            // 无记录的子进程，但可能仍有残留后端，兜底清理
-            kill_all_backend_processes();
+            app_log!(error, "自动准备FFmpeg失败: {}", &e);
         }
-        Ok(false) // 没有运行的进程
     }
+
+    let use_packaged_backend = !prefer_python_backend && backend_executable.is_file();
+    let (script, launch_error) = if use_packaged_backend {
+        (None, None)
+    } else if is_dev_mode || forced_backend_script.is_some() {
+        match forced_backend_script
+            .or_else(|| find_backend_script(&resource_dir))
+            .filter(|p| p.is_file())
+        {
+            Some(p) => (Some(p), None),
+            None => (None, Some("后端脚本不存在: backend/main.py".to_string())),
+        }
+    } else {
+        (
+            None,
+            Some("未找到打包的后端可执行文件，请检查打包配置 bundle.resources".to_string()),
+        )
+    };
+    if let Some(e) = &launch_error {
+        notes.push(e.clone());
+    }
+
+    // 预检不回退随机端口（避免写端口缓存），固定范围均被占用时返回 0
+    let forced_port = forced_backend_port(backend);
+    let port = match forced_port {
+        Some(p) => p,
+        None if dry_run => scan_backend_port(is_dev_mode)
+            .or_else(|| is_dev_mode.then(|| configured_dev_port().unwrap_or(DEFAULT_DEV_PORT)))
+            .unwrap_or(0),
+        None => choose_backend_port(is_dev_mode),
+    };
+
+    Ok(LaunchPlan {
+        resource_dir,
+        resource_root,
+        resource_decision,
+        force_packaged_backend,
+        backend_executable,
+        use_packaged_backend,
+        script,
+        launch_error,
+        port,
+        port_forced: forced_port.is_some(),
+        notes,
+    })
+}
+
+// 后端启动预检计划：start_backend 将使用的可执行文件/脚本、解释器、端口与环境变量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackendLaunchPlan {
+    mode: String,
+    executable: Option<String>,
+    executable_exists: bool,
+    script: Option<String>,
+    script_exists: bool,
+    python_cmd: Option<String>,
+    python_available: bool,
+    port: u16,
+    port_forced: bool,
+    port_available: bool,
+    env: Vec<(String, String)>,
+    notes: Vec<String>,
 }
 
-// Tauri命令：获取后端状态
+// Tauri命令：dry-run 探测后端启动计划，与 start_backend 共用 plan_backend_launch 的决策，只做定位与检查，不解压、不下载、不写缓存、不 spawn
 #[tauri::command]
-async fn get_backend_status(state: State<'_, AppState>) -> Result<BackendStatus, String> {
-    let mut process_guard = state.backend_process.lock().unwrap();
+async fn probe_backend_launch(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    profile: Option<String>,
+) -> Result<BackendLaunchPlan, String> {
+    validate_profile(&profile)?;
+    // 未启动过的 profile 用临时实例推演，不登记到 AppState
+    let backend = state
+        .existing_backend(profile.as_deref())
+        .unwrap_or_else(|_| BackendInstance::new(profile_name(profile.as_deref())));
+    let host = "127.0.0.1";
+    // 预检不写入实例的启动轨迹
+    let scratch_trace = Mutex::new(HashMap::new());
+    let plan = plan_backend_launch(&app_handle, &state, &backend, &scratch_trace, true)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut notes = plan.notes;
+    let mode = if plan.use_packaged_backend {
+        "packaged"
+    } else if plan.script.is_some() {
+        "python"
+    } else {
+        "none"
+    };
+    let python_cmd = plan
+        .script
+        .as_ref()
+        .map(|s| select_python_cmd(s.parent().unwrap_or_else(|| std::path::Path::new("."))));
+    let python_check = python_cmd.as_deref().map(check_python_version);
+    let python_available = matches!(python_check, Some(Ok(_)));
+    if let Some(Err(e)) = python_check {
+        notes.push(e);
+    }
 
-    if let Some(ref mut child) = *process_guard {
-        match child.try_wait() {
-            Ok(Some(_)) => {
-                // 进程已退出
-                *process_guard = None;
-                Ok(BackendStatus {
-                    running: false,
-                    port: 0,
-                    pid: None,
+    let port = plan.port;
+    let port_available = port != 0 && is_port_available(port);
+    if port == 0 {
+        notes.push("固定端口范围均被占用，实际启动时将使用系统分配的随机端口".to_string());
+    } else if !port_available {
+        notes.push(format!("端口 {} 已被占用", port));
+    }
+
+    let backend_tmp_dir_s = backend_tmp_dir(&app_handle).to_string_lossy().to_string();
+    let mut env: Vec<(String, String)> = vec![
+        ("HOST".to_string(), host.to_string()),
+        ("PORT".to_string(), port.to_string()),
+        ("TEMP".to_string(), backend_tmp_dir_s.clone()),
+        ("TMP".to_string(), backend_tmp_dir_s),
+        ("SACV_RUNTIME".to_string(), "tauri".to_string()),
+    ];
+    if let Some(dir) = offline_ffmpeg_dir() {
+        env.push((
+            "SACV_FFMPEG_DIR".to_string(),
+            dir.to_string_lossy().to_string(),
+        ));
+    }
+    if let Some(output_root) = state.output_root.lock().unwrap().clone() {
+        env.push(("SACV_OUTPUT_ROOT".to_string(), output_root));
+    }
+
+    notes.push(format!(
+        "resource_root 决策: {}",
+        plan.resource_decision.describe()
+    ));
+
+    Ok(BackendLaunchPlan {
+        mode: mode.to_string(),
+        executable: Some(plan.backend_executable.to_string_lossy().to_string()),
+        executable_exists: plan.backend_executable.is_file(),
+        script_exists: plan.script.as_ref().map(|s| s.is_file()).unwrap_or(false),
+        script: plan.script.map(|s| s.to_string_lossy().to_string()),
+        python_cmd,
+        python_available,
+        port,
+        port_forced: plan.port_forced,
+        port_available,
+        env,
+        notes,
+    })
+}
+
+// 首次运行预检的单项结果：status 为 pass/warn/fail，suggestion 为修复建议
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PreflightItem {
+    name: String,
+    status: String,
+    message: String,
+    suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PreflightReport {
+    overall: String,
+    items: Vec<PreflightItem>,
+    // FFmpeg 缺失时为 false：应用仍可启动，前端据此置灰依赖 FFmpeg 的功能并引导安装
+    ffmpeg_ready: bool,
+}
+
+fn preflight_item(
+    name: &str,
+    status: &str,
+    message: String,
+    suggestion: Option<&str>,
+) -> PreflightItem {
+    PreflightItem {
+        name: name.to_string(),
+        status: status.to_string(),
+        message,
+        suggestion: suggestion.map(|s| s.to_string()),
+    }
+}
+
+const PREFLIGHT_DISK_WARN_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+const PREFLIGHT_DISK_FAIL_BYTES: u64 = 500 * 1024 * 1024;
+
+// Tauri命令：首次运行引导预检（后端定位、FFmpeg、端口、磁盘空间、Windows VC++ 运行库）
+#[tauri::command]
+async fn preflight_check(app_handle: AppHandle) -> Result<PreflightReport, String> {
+    let mut items: Vec<PreflightItem> = Vec::new();
+    let plan =
+        probe_backend_launch(app_handle.state::<AppState>(), app_handle.clone(), None).await?;
+
+    let backend_item = match plan.mode.as_str() {
+        "packaged" => preflight_item(
+            "backend",
+            "pass",
+            format!(
+                "后端可执行文件: {}",
+                plan.executable.clone().unwrap_or_default()
+            ),
+            None,
+        ),
+        "python" if plan.python_available => preflight_item(
+            "backend",
+            "pass",
+            format!("后端脚本: {}", plan.script.clone().unwrap_or_default()),
+            None,
+        ),
+        "python" => preflight_item(
+            "backend",
+            "fail",
+            plan.notes.join("；"),
+            Some("安装 Python 3.9 及以上版本，或通过 BACKEND_PYTHON 指定解释器路径"),
+        ),
+        _ => preflight_item(
+            "backend",
+            "fail",
+            plan.notes.join("；"),
+            Some("重新安装应用，或检查安装目录是否被安全软件隔离"),
+        ),
+    };
+    items.push(backend_item);
+
+    let ffmpeg = resolve_ffmpeg_tool(&app_handle, "ffmpeg");
+    let ffprobe = resolve_ffmpeg_tool(&app_handle, "ffprobe");
+    let ffmpeg_ready = ffmpeg.is_ok() && ffprobe.is_ok();
+    items.push(match (&ffmpeg, &ffprobe) {
+        (Ok(a), Ok(b)) => preflight_item(
+            "ffmpeg",
+            "pass",
+            format!("{} / {}", a.to_string_lossy(), b.to_string_lossy()),
+            None,
+        ),
+        // 缺少 FFmpeg 不阻止启动（Windows 打包版首次启动还会自动下载），仅提示部分功能不可用
+        (Err(e), _) | (_, Err(e)) => preflight_item(
+            "ffmpeg",
+            "warn",
+            format!("FFmpeg 未就绪，部分功能不可用: {}", e),
+            Some("安装 FFmpeg 并加入 PATH，或设置 SACV_FFMPEG_DIR 指向包含 ffmpeg/ffprobe 的目录"),
+        ),
+    });
+
+    items.push(if plan.port == 0 {
+        preflight_item(
+            "port",
+            "warn",
+            "首选端口范围均被占用，将使用系统分配的随机端口".to_string(),
+            Some("关闭占用 18000-18100 端口的程序"),
+        )
+    } else if plan.port_available {
+        preflight_item("port", "pass", format!("端口 {} 可用", plan.port), None)
+    } else {
+        preflight_item(
+            "port",
+            if plan.port_forced { "fail" } else { "warn" },
+            format!("端口 {} 已被占用", plan.port),
+            Some("关闭占用该端口的程序，或通过 set_backend_port 指定其他端口"),
+        )
+    });
+
+    let data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| std::env::temp_dir());
+    items.push(match available_disk_space(&data_dir) {
+        Some(free) if free < PREFLIGHT_DISK_FAIL_BYTES => preflight_item(
+            "disk",
+            "fail",
+            format!("可用磁盘空间仅 {} MB", free / 1024 / 1024),
+            Some("清理磁盘空间后再使用"),
+        ),
+        Some(free) if free < PREFLIGHT_DISK_WARN_BYTES => preflight_item(
+            "disk",
+            "warn",
+            format!(
+                "可用磁盘空间 {} MB，处理大视频时可能不足",
+                free / 1024 / 1024
+            ),
+            Some("建议保留至少 2 GB 可用空间"),
+        ),
+        Some(free) => preflight_item(
+            "disk",
+            "pass",
+            format!("可用磁盘空间 {} MB", free / 1024 / 1024),
+            None,
+        ),
+        None => preflight_item("disk", "warn", "无法获取磁盘可用空间".to_string(), None),
+    });
+
+    #[cfg(target_os = "windows")]
+    {
+        let missing = missing_vc_runtime_dlls();
+        items.push(if missing.is_empty() {
+            preflight_item("vc_runtime", "pass", "VC++ 运行库已安装".to_string(), None)
+        } else {
+            preflight_item(
+                "vc_runtime",
+                "fail",
+                format!("缺少运行库: {}", missing.join(", ")),
+                Some("安装 Microsoft Visual C++ 2015-2022 运行库 (x64)"),
+            )
+        });
+    }
+
+    let overall = if items.iter().any(|i| i.status == "fail") {
+        "fail"
+    } else if items.iter().any(|i| i.status == "warn") {
+        "warn"
+    } else {
+        "pass"
+    };
+    Ok(PreflightReport {
+        overall: overall.to_string(),
+        items,
+        ffmpeg_ready,
+    })
+}
+
+// Tauri命令：启动Python后端，并据结果维护该实例的生命周期状态
+#[tauri::command]
+async fn start_backend(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    profile: Option<String>,
+) -> Result<BackendStatus, BackendError> {
+    validate_profile(&profile)?;
+    let backend = state.backend(profile.as_deref());
+    // 已有启动流程在进行时由该流程负责状态流转
+    let owns_start = !backend.backend_starting.load(Ordering::SeqCst);
+    if owns_start {
+        match backend.lifecycle() {
+            BackendLifecycle::Ready => {}
+            BackendLifecycle::Crashed => {
+                backend.set_lifecycle(&app_handle, BackendLifecycle::Reconnecting)
+            }
+            BackendLifecycle::Reconnecting => {}
+            _ => backend.set_lifecycle(&app_handle, BackendLifecycle::Starting),
+        }
+    }
+    let timeout_secs = backend_start_timeout_secs(&app_handle);
+    let mut result = match tokio::time::timeout(
+        Duration::from_secs(timeout_secs),
+        launch_backend(state, app_handle.clone(), profile),
+    )
+    .await
+    {
+        Ok(r) => r,
+        Err(_) => Err(abort_timed_out_start(&app_handle, &backend, timeout_secs).await),
+    };
+    match &result {
+        Ok(status) if status.source == "reused" => {
+            if status.pid.is_some() {
+                backend.set_lifecycle(&app_handle, BackendLifecycle::Ready);
+            }
+        }
+        Ok(status) if status.running => backend.set_lifecycle(&app_handle, BackendLifecycle::Ready),
+        Ok(_) => backend.set_lifecycle(&app_handle, BackendLifecycle::Stopped),
+        Err(BackendError::AlreadyStarting { .. }) => {}
+        // 自动重连期间单次失败不改变状态，由重连循环决定最终结果，避免 crashed/reconnecting 来回跳
+        Err(_) if backend.reconnect_active.load(Ordering::SeqCst) => {}
+        Err(BackendError::EarlyExit { .. })
+        | Err(BackendError::ReadyTimeout { .. })
+        | Err(BackendError::StartTimeout { .. }) => {
+            backend.set_lifecycle(&app_handle, BackendLifecycle::Crashed)
+        }
+        Err(_) => backend.set_lifecycle(&app_handle, BackendLifecycle::Stopped),
+    }
+    // 就绪后拉取一次 server/info 缓存版本与能力标志，随 BackendStatus 一并返回
+    if let Ok(status) = &mut result {
+        // 端口确定（本次拉起、发现或从日志解析）时广播，复用已有进程时端口未变化不重复发送
+        if status.running && status.port != 0 && status.source != "reused" {
+            let _ = app_handle.emit(
+                "backend-port",
+                serde_json::json!({ "profile": backend.profile, "port": status.port, "source": status.source }),
+            );
+        }
+        if status.running {
+            match fetch_backend_info(&backend).await {
+                Ok(info) => status.server_info = Some(info),
+                Err(e) => app_log!(warn, "[backend] 就绪后获取后端信息失败: {}", e),
+            }
+        }
+    }
+    // 后端已起但 FFmpeg 缺失时不视为启动失败，通知前端置灰相关功能
+    if matches!(&result, Ok(status) if status.running) {
+        if let Err(e) = resolve_ffmpeg_tool(&app_handle, "ffmpeg") {
+            app_log!(warn, "[backend] FFmpeg 未就绪，部分功能不可用: {}", e);
+            let _ = app_handle.emit(
+                "ffmpeg-unavailable",
+                serde_json::json!({ "profile": backend.profile, "message": e }),
+            );
+        }
+    }
+    result
+}
+
+const DEFAULT_BACKEND_START_TIMEOUT_SECS: u64 = 180;
+const MIN_BACKEND_START_TIMEOUT_SECS: u64 = 30;
+
+// 启动流程总超时（秒）：SACV_START_TIMEOUT_SECS 优先，其次配置 start_timeout_secs，默认 180，最小 30
+fn backend_start_timeout_secs(app_handle: &AppHandle) -> u64 {
+    std::env::var("SACV_START_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .or_else(|| {
+            load_app_config(app_handle)
+                .get("start_timeout_secs")
+                .and_then(|v| v.as_u64())
+        })
+        .unwrap_or(DEFAULT_BACKEND_START_TIMEOUT_SECS)
+        .max(MIN_BACKEND_START_TIMEOUT_SECS)
+}
+
+// 启动流程超时：丢弃启动 future 即取消其中正在等待的下载/就绪探测，这里再释放启动标志、
+// 停掉已拉起但未就绪的进程（后台解压线程会自行跑完），返回带阶段信息的错误
+async fn abort_timed_out_start(
+    app_handle: &AppHandle,
+    backend: &BackendInstance,
+    timeout_secs: u64,
+) -> BackendError {
+    let stage = *backend.start_stage.lock().unwrap();
+    let message = format!("启动后端超时（{} 秒），卡在阶段: {}", timeout_secs, stage);
+    app_log!(error, "[backend] {}", message);
+    log_meta(&backend.backend_boot_trace, "start_timeout_stage", stage);
+    backend.backend_starting.store(false, Ordering::SeqCst);
+    if backend.backend_process.lock().unwrap().is_some() {
+        let _ = stop_backend_process_async(app_handle, Some(backend.profile.clone())).await;
+    }
+    *backend.start_stage.lock().unwrap() = "idle";
+    BackendError::StartTimeout {
+        stage: stage.to_string(),
+        message,
+    }
+}
+
+async fn launch_backend(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    profile: Option<String>,
+) -> Result<BackendStatus, BackendError> {
+    let backend = state.backend(profile.as_deref());
+    let early_log_path = backend_log_path();
+    let _ = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&early_log_path);
+    append_log_line(
+        early_log_path.clone(),
+        &format!("[meta] start_backend invoked profile={}", backend.profile),
+    );
+
+    // 最早期并发启动防护：若已有启动流程进行中，则等待其更新状态，避免重复拉起
+    // 持有启动标志的流程若卡死超过阈值（如下载挂起），视为陈旧标志，由本次启动接管
+    if backend.backend_starting.swap(true, Ordering::SeqCst) {
+        let stale = backend
+            .backend_starting_since
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|t| t.elapsed() > Duration::from_secs(BACKEND_STARTING_STALE_SECS))
+            .unwrap_or(false);
+        if stale {
+            append_log_line(
+                early_log_path.clone(),
+                "[meta] stale backend_starting flag taken over",
+            );
+        } else {
+            for _ in 0..40 {
+                tokio::time::sleep(Duration::from_millis(150)).await;
+                let port = *backend.backend_port.lock().unwrap();
+                let boot_token = backend.backend_boot_token.lock().unwrap().clone();
+                let process_guard = backend.backend_process.lock().unwrap();
+                let running = process_guard.is_some() || port != 0;
+                drop(process_guard);
+                if running {
+                    if port != 0 {
+                        app_log!(
+                            info,
+                            "[backend] 启动中（复用已有启动流程）：http://127.0.0.1:{}",
+                            port
+                        );
+                    }
+                    return Ok(BackendStatus {
+                        running,
+                        port,
+                        pid: None,
+                        boot_token,
+                        source: "reused".to_string(),
+                        ready_ms: None,
+                        server_info: None,
+                    });
+                }
+            }
+            return Err(BackendError::AlreadyStarting {
+                message: "后端正在启动中，请稍后重试".to_string(),
+            });
+        }
+    }
+    *backend.backend_starting_since.lock().unwrap() = Some(std::time::Instant::now());
+    *backend.start_stage.lock().unwrap() = "discovery";
+    let trace = backend.backend_boot_trace.clone();
+    trace.lock().unwrap().clear();
+
+    // 先短暂持锁检查和清理状态，避免并发重复启动
+    {
+        let mut process_guard = backend.backend_process.lock().unwrap();
+        if let Some(ref mut child) = *process_guard {
+            match child.try_wait() {
+                Ok(Some(_)) => {
+                    // 进程已退出，清理
+                    *process_guard = None;
+                }
+                Ok(None) => {
+                    // 进程仍在运行
+                    let port = *backend.backend_port.lock().unwrap();
+                    let boot_token = backend.backend_boot_token.lock().unwrap().clone();
+                    app_log!(
+                        info,
+                        "[backend] 已在运行：http://127.0.0.1:{} (pid={})",
+                        port,
+                        child.id()
+                    );
+                    return Ok(BackendStatus {
+                        running: true,
+                        port,
+                        pid: Some(child.id()),
+                        boot_token,
+                        source: "reused".to_string(),
+                        ready_ms: None,
+                        server_info: None,
+                    });
+                }
+                Err(_) => {
+                    // 检查失败，清理
+                    *process_guard = None;
+                }
+            }
+        }
+    }
+
+    let host = "127.0.0.1";
+    let is_dev_mode =
+        cfg!(debug_assertions) || std::env::var("TAURI_DEV").ok().as_deref() == Some("1");
+    let forced_port_opt = forced_backend_port(&backend);
+    // UDS 模式下不做端口扫描发现，始终由本应用拉起监听 socket 的后端
+    let uds_path = prepare_backend_uds_path(&app_handle, &backend.profile);
+    *backend.backend_uds_path.lock().unwrap() = uds_path.clone();
+    // 强制端口（SACV_FORCE_PORT 或 set_backend_port）被占用时后端必然绑定失败，在解压/启动前立即报错而不是等到超时
+    if let (Some(p), None) = (forced_port_opt, &uds_path) {
+        if !is_port_available(p) {
+            backend.backend_starting.store(false, Ordering::SeqCst);
+            let message = match describe_port_owner(p) {
+                Some(owner) => format!("强制端口 {} 不可用（被 {} 占用）", p, owner),
+                None => format!("强制端口 {} 不可用（已被占用）", p),
+            };
+            log_meta(&trace, "forced_port_unavailable", &message);
+            return Err(BackendError::PortUnavailable { message });
+        }
+    }
+    // 非默认 profile 总是拉起独立实例；默认实例发现已有后端时跳过其它 profile 占用的端口，以及用户选择不复用的其它安装的后端
+    let mut other_ports: Vec<u16> = state
+        .other_backends(&backend.profile)
+        .iter()
+        .map(|b| *b.backend_port.lock().unwrap())
+        .filter(|p| *p != 0)
+        .collect();
+    other_ports.extend(state.ignored_backend_ports.lock().unwrap().iter().copied());
+    // SACV_NO_DISCOVER=1：忽略已运行的后端，总是拉起全新实例（dev 模式反复重启时避免复用即将被替换的旧实例）
+    let no_discover = std::env::var("SACV_NO_DISCOVER").ok().as_deref() == Some("1");
+    if no_discover {
+        log_meta(&trace, "discovery_disabled", "SACV_NO_DISCOVER=1");
+    }
+    let allow_discovery =
+        backend.is_default() && forced_port_opt.is_none() && uds_path.is_none() && !no_discover;
+    if is_dev_mode && allow_discovery {
+        if let Some((p, boot_token)) = discover_existing_backend(host, false)
+            .await
+            .filter(|(p, _)| !other_ports.contains(p))
+        {
+            app_log!(info, "[backend] 已发现运行中的后端：http://{}:{}", host, p);
+            return Ok(record_discovered_backend(&backend, p, boot_token, None));
+        }
+    }
+    // 生产环境也尝试发现已运行的后端，避免重复启动
+    if !is_dev_mode && allow_discovery {
+        if let Some((p, boot_token)) = discover_existing_backend_quick(host, true)
+            .await
+            .filter(|(p, _)| !other_ports.contains(p))
+        {
+            app_log!(info, "[backend] 已发现运行中的后端：http://{}:{}", host, p);
+            return Ok(record_discovered_backend(&backend, p, boot_token, None));
+        }
+    }
+
+    let plan = plan_backend_launch(&app_handle, &state, &backend, &trace, false).await?;
+    let LaunchPlan {
+        resource_dir,
+        resource_root,
+        force_packaged_backend,
+        backend_executable,
+        use_packaged_backend,
+        script,
+        launch_error,
+        port,
+        ..
+    } = plan;
+    let install_dir = app_install_dir(&resource_dir);
+
+    if use_packaged_backend && cfg!(unix) {
+        let exe_clone = backend_executable.clone();
+        let result = tauri::async_runtime::spawn_blocking(move || {
+            ensure_backend_exec_permissions(&exe_clone)
+        })
+        .await
+        .map_err(|e| format!("检查后端执行权限任务异常: {}", e))
+        .and_then(|r| r);
+        match result {
+            Ok(0) => {}
+            Ok(n) => {
+                log_meta(&trace, "backend_exec_bits_repaired", n);
+                app_log!(warn, "[backend] 已为 {} 个后端文件补上执行权限", n);
+            }
+            Err(e) => {
+                log_meta(&trace, "backend_exec_bits_error", &e);
+                append_log_line(backend_log_path(), &format!("[error] {}", e));
+                return Err(BackendError::ExecutableNotFound {
+                    message: format!("后端可执行文件缺少执行权限且无法修复: {}", e),
+                });
+            }
+        }
+    }
+    let mut cmd = if use_packaged_backend {
+        // 使用打包的可执行文件
+        log_meta(&trace, "use_packaged_backend_exe", 1);
+        app_log!(info, "使用打包的后端可执行文件: {:?}", backend_executable);
+        let backend_working_dir = backend_executable
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| resource_root.clone());
+        let mut c = apply_windows_no_window(Command::new(&backend_executable));
+        c.current_dir(backend_working_dir);
+        c
+    } else if let Some(backend_script) = script {
+        log_meta(
+            &trace,
+            "use_python_backend_script",
+            backend_script.to_string_lossy(),
+        );
+        app_log!(info, "使用Python运行后端脚本: {:?}", backend_script);
+        match build_python_backend_command(&backend_script, &trace) {
+            Ok(c) => c,
+            Err(e) => {
+                backend.backend_starting.store(false, Ordering::SeqCst);
+                append_log_line(backend_log_path(), &format!("[error] {}", e));
+                return Err(BackendError::ExecutableNotFound { message: e });
+            }
+        }
+    } else {
+        let err = launch_error.unwrap_or_else(|| "未找到可用的后端".to_string());
+        append_log_line(backend_log_path(), &format!("[error] {}", err));
+        return Err(BackendError::ExecutableNotFound { message: err });
+    };
+
+    // 设置环境变量
+    let boot_token = generate_boot_token();
+    let orig_path = std::env::var("PATH").unwrap_or_default();
+    let sep = if cfg!(target_os = "windows") {
+        ";"
+    } else {
+        ":"
+    };
+    let backend_dir = backend_executable
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| resource_root.to_string_lossy().to_string());
+    let resource_dir_s = resource_root.to_string_lossy().to_string();
+    let internal_dir_s = backend_executable
+        .parent()
+        .map(|p| p.join("_internal"))
+        .filter(|p| p.exists())
+        .map(|p| p.to_string_lossy().to_string());
+    // 离线放置的 FFmpeg 目录前置到 PATH，便于后端直接找到
+    let mut path_entries: Vec<String> = Vec::new();
+    if let Some(dir) = offline_ffmpeg_dir() {
+        path_entries.push(dir.to_string_lossy().to_string());
+    }
+    path_entries.push(backend_dir);
+    path_entries.push(resource_dir_s);
+    if let Some(internal) = internal_dir_s {
+        path_entries.push(internal);
+    }
+    // 用户自定义依赖目录排在后端自身目录之后、原 PATH 之前
+    let extra_path_dirs = backend_extra_path_dirs(&app_handle);
+    if !extra_path_dirs.is_empty() {
+        log_meta(&trace, "extra_path", extra_path_dirs.join(sep));
+        path_entries.extend(extra_path_dirs);
+    }
+    path_entries.extend(orig_path.split(sep).map(|e| e.to_string()));
+    let new_path = join_path_entries(&path_entries, sep);
+    log_meta(&trace, "backend_path_len", new_path.len());
+    if new_path.len() > MAX_BACKEND_PATH_LEN {
+        append_log_line(
+            backend_log_path(),
+            &format!(
+                "[warn] backend PATH 长度 {} 超过 {}，子进程可能启动失败",
+                new_path.len(),
+                MAX_BACKEND_PATH_LEN
+            ),
+        );
+    }
+    let backend_tmp_dir = backend_tmp_dir(&app_handle);
+    let _ = std::fs::create_dir_all(&backend_tmp_dir);
+    let backend_tmp_dir_s = backend_tmp_dir.to_string_lossy().to_string();
+    *backend.backend_port.lock().unwrap() = port;
+    register_boot_token(&boot_token);
+    *backend.backend_boot_token.lock().unwrap() = Some(boot_token.clone());
+    let mut launch_env: Vec<(String, String)> = vec![
+        ("HOST".to_string(), host.to_string()),
+        ("PORT".to_string(), port.to_string()),
+        ("PATH".to_string(), new_path),
+        ("TEMP".to_string(), backend_tmp_dir_s.clone()),
+        ("TMP".to_string(), backend_tmp_dir_s),
+        ("SACV_BOOT_TOKEN".to_string(), boot_token.clone()),
+        ("SACV_RUNTIME".to_string(), "tauri".to_string()),
+        ("SACV_PROFILE".to_string(), backend.profile.clone()),
+        (
+            "SACV_INSTALL_DIR".to_string(),
+            install_dir
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        ),
+    ];
+    if let Some(output_root) = state.output_root.lock().unwrap().clone() {
+        log_meta(&trace, "output_root", &output_root);
+        launch_env.push(("SACV_OUTPUT_ROOT".to_string(), output_root));
+    }
+    if let Some(sock) = &uds_path {
+        log_meta(&trace, "uds_path", sock.to_string_lossy());
+        launch_env.push((
+            "SACV_UDS_PATH".to_string(),
+            sock.to_string_lossy().to_string(),
+        ));
+    }
+    log_meta(&trace, "profile", &backend.profile);
+    if let Some(level) = load_app_config(&app_handle)
+        .get("backend_log_level")
+        .and_then(|v| v.as_str())
+        .filter(|l| BACKEND_LOG_LEVELS.contains(l))
+    {
+        log_meta(&trace, "log_level", level);
+        launch_env.push(("SACV_LOG_LEVEL".to_string(), level.to_string()));
+    }
+    let lang = app_language(&app_handle);
+    log_meta(&trace, "lang", &lang);
+    launch_env.push(("SACV_LANG".to_string(), lang));
+    let profile_env = backend_profile_env(&app_handle, &backend.profile);
+    if !profile_env.is_empty() {
+        let keys: Vec<&str> = profile_env.iter().map(|(k, _)| k.as_str()).collect();
+        log_meta(&trace, "profile_env_keys", keys.join(","));
+        launch_env.extend(profile_env);
+    }
+    apply_backend_launch_env(&mut cmd, &launch_env);
+    record_launch_env(&backend, &launch_env);
+    let extra_args = backend_extra_args(&app_handle);
+    if !extra_args.is_empty() {
+        log_meta(&trace, "backend_args", extra_args.join(" "));
+        cmd.args(&extra_args);
+    }
+
+    // 启动进程；打包后端 spawn 失败或启动后立即崩溃时，若存在源码后端则降级用 Python 启动
+    *backend.start_stage.lock().unwrap() = "spawn";
+    let mut spawned_at = std::time::Instant::now();
+    let mut spawn_result = cmd.spawn();
+    // 强制打包后端时不降级到源码后端，便于对比两种后端的真实表现
+    let fallback_script = if use_packaged_backend && !force_packaged_backend {
+        find_backend_script(&resource_dir)
+    } else {
+        None
+    };
+    if let Some(script) = fallback_script {
+        let packaged_failure = match &mut spawn_result {
+            Err(e) => Some(format!("spawn_failed: {}", e)),
+            Ok(child) => {
+                let mut exited = None;
+                for _ in 0..15 {
+                    if let Ok(Some(status)) = child.try_wait() {
+                        exited = Some(status);
+                        break;
+                    }
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                exited.map(|status| format!("early_exit: {}", status))
+            }
+        };
+        if let Some(reason) = packaged_failure {
+            if let Ok(child) = &mut spawn_result {
+                // 在独立线程中转存已退出的打包后端的 stderr：若有子进程继承了管道，读取不会结束，不能阻塞异步运行时
+                if let Some(stderr) = child.stderr.take() {
+                    let handle = thread::spawn(move || {
+                        for l in BufReader::new(stderr).lines().map_while(Result::ok) {
+                            append_log_line(backend_log_path(), &format!("[stderr] {}", l));
+                        }
+                    });
+                    backend.log_threads.lock().unwrap().push(handle);
+                }
+            }
+            log_meta(&trace, "packaged_backend_failure", &reason);
+            log_meta(
+                &trace,
+                "fallback_python_backend_script",
+                script.to_string_lossy(),
+            );
+            app_log!(
+                info,
+                "打包后端启动失败（{}），降级使用Python运行后端脚本: {:?}",
+                reason,
+                script
+            );
+            match build_python_backend_command(&script, &trace) {
+                Ok(mut fallback) => {
+                    apply_backend_launch_env(&mut fallback, &launch_env);
+                    fallback.args(&extra_args);
+                    spawned_at = std::time::Instant::now();
+                    spawn_result = fallback.spawn();
+                }
+                Err(e) => append_log_line(backend_log_path(), &format!("[error] {}", e)),
+            }
+        }
+    }
+    match spawn_result {
+        Ok(mut child) => {
+            #[cfg(target_os = "windows")]
+            {
+                let job = attach_backend_job(&child);
+                if let Some(old) = std::mem::replace(&mut *backend.backend_job.lock().unwrap(), job)
+                {
+                    unsafe {
+                        CloseHandle(old as *mut std::ffi::c_void);
+                    }
+                }
+            }
+            app_log!(
+                info,
+                "[backend] 已启动进程，等待就绪：http://{}:{} (pid={})",
+                host,
+                port,
+                child.id()
+            );
+            // 捕获日志到临时文件
+            let log_path = backend_log_path();
+            let _ = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&log_path);
+            // 上一轮进程的读取线程应已随管道关闭退出，先回收再创建新线程
+            backend.reap_log_threads(Duration::ZERO);
+            if let Some(stdout) = child.stdout.take() {
+                let path_clone = log_path.clone();
+                let app_clone = app_handle.clone();
+                let handle = thread::spawn(move || {
+                    let reader = BufReader::new(stdout);
+                    for line in reader.lines() {
+                        if let Ok(l) = line {
+                            if let Some(event) = parse_backend_event_line(&l) {
+                                let _ = app_clone.emit("backend-event", event);
+                                continue;
+                            }
+                            append_log_line(path_clone.clone(), &format!("[stdout] {}", l));
+                        }
+                    }
+                });
+                backend.log_threads.lock().unwrap().push(handle);
+            }
+            let startup_error: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+            backend.stderr_tail.lock().unwrap().clear();
+            if let Some(stderr) = child.stderr.take() {
+                let path_clone = log_path.clone();
+                let startup_error = startup_error.clone();
+                let stderr_tail = backend.stderr_tail.clone();
+                let handle = thread::spawn(move || {
+                    let reader = BufReader::new(stderr);
+                    for line in reader.lines() {
+                        if let Ok(l) = line {
+                            append_log_line(path_clone.clone(), &format!("[stderr] {}", l));
+                            {
+                                let mut tail = stderr_tail.lock().unwrap();
+                                if tail.len() >= CRASH_SNAPSHOT_TAIL_LINES {
+                                    tail.pop_front();
+                                }
+                                tail.push_back(l.clone());
+                            }
+                            if is_python_startup_error(&l) {
+                                let mut guard = startup_error.lock().unwrap();
+                                if guard.is_none() {
+                                    *guard = Some(l.trim().to_string());
+                                }
+                            }
+                        }
+                    }
+                });
+                backend.log_threads.lock().unwrap().push(handle);
+            }
+
+            let pid = child.id();
+            {
+                let mut process_guard = backend.backend_process.lock().unwrap();
+                *process_guard = Some(child);
+            }
+            spawn_backend_exit_monitor(&app_handle, &backend, pid);
+            backend.backend_starting.store(false, Ordering::SeqCst);
+
+            *backend.start_stage.lock().unwrap() = "wait_ready";
+            // 等待后端就绪（最多 60 秒，避免首次解压或冷启动偏慢）
+            let ready = match wait_for_backend_ready(
+                &app_handle,
+                &backend,
+                host,
+                port,
+                60,
+                pid,
+                &startup_error,
+            )
+            .await
+            {
+                Ok(()) => true,
+                Err(e) => {
+                    log_meta(&trace, "wait_ready_error", format!("{:?}", e));
+                    false
+                }
+            };
+            // stderr 的启动错误只有在进程确已退出时才作为失败原因
+            let startup_failure = startup_error
+                .lock()
+                .unwrap()
+                .clone()
+                .and_then(|err| backend_child_exit(&backend, pid).map(|code| (err, code)));
+            if ready {
+                app_log!(
+                    info,
+                    "[backend] 已就绪：http://{}:{} ({}ms)",
+                    host,
+                    port,
+                    spawned_at.elapsed().as_millis()
+                );
+                let _ = tauri_plugin_notification::NotificationExt::notification(&app_handle)
+                    .builder()
+                    .title("AI智能视频剪辑")
+                    .body("后端服务启动成功")
+                    .show();
+
+                *backend.backend_source.lock().unwrap() = "spawned".to_string();
+                Ok(BackendStatus {
+                    running: true,
+                    port,
+                    pid: Some(pid),
+                    boot_token: Some(boot_token),
+                    source: "spawned".to_string(),
+                    ready_ms: Some(spawned_at.elapsed().as_millis() as u64),
+                    server_info: None,
+                })
+            } else if let Some((err, exit_code)) = startup_failure {
+                log_meta(&trace, "python_startup_error", &err);
+                Err(BackendError::EarlyExit {
+                    exit_code,
+                    message: format!("后端启动失败: {}", err),
+                })
+            } else {
+                // 超时未就绪，尝试从日志解析实际监听端口
+                if let Some(found_port) = parse_backend_port_from_log() {
+                    *backend.backend_port.lock().unwrap() = found_port;
+                    app_log!(
+                        info,
+                        "[backend] 从日志解析到监听端口：http://{}:{}",
+                        host,
+                        found_port
+                    );
+                    *backend.backend_source.lock().unwrap() = "log-parsed".to_string();
+                    Ok(BackendStatus {
+                        running: true,
+                        port: found_port,
+                        pid: Some(pid),
+                        boot_token: backend.backend_boot_token.lock().unwrap().clone(),
+                        source: "log-parsed".to_string(),
+                        ready_ms: None,
+                        server_info: None,
+                    })
+                } else {
+                    if let Some((found_port, found_token)) =
+                        discover_existing_backend_quick(host, !is_dev_mode || no_discover)
+                            .await
+                            .filter(|(p, _)| !other_ports.contains(p))
+                    {
+                        app_log!(
+                            info,
+                            "[backend] 已发现运行中的后端：http://{}:{}",
+                            host,
+                            found_port
+                        );
+                        Ok(record_discovered_backend(
+                            &backend,
+                            found_port,
+                            found_token,
+                            Some(pid),
+                        ))
+                    } else {
+                        // 进程已退出（已被退出监控回收时退出码未知）则报告 EarlyExit
+                        let exited = backend_child_exit(&backend, pid);
+                        let hint = if use_packaged_backend {
+                            diagnose_backend_dlls(&backend_executable)
+                        } else {
+                            None
+                        };
+                        if let Some(exit_code) = exited {
+                            let mut message = format!(
+                                "后端进程启动后意外退出（退出码 {:?}）；请查看临时日志 super_auto_cut_backend.log",
+                                exit_code
+                            );
+                            if let Some(hint) = hint {
+                                message = format!("{}；{}", message, hint);
+                            }
+                            return Err(BackendError::EarlyExit { exit_code, message });
+                        }
+                        // 未发现已就绪端口，保留已启动的进程，返回错误以提示检查日志，但不杀进程
+                        let mut err = "后端服务启动超时，但进程已保留；请查看临时日志 super_auto_cut_backend.log".to_string();
+                        if let Some(hint) = hint {
+                            err = format!("{}；{}", err, hint);
+                        }
+                        // 后端运行中找不到 ffmpeg 也会卡在未就绪；定位不到时立即返回提示，
+                        // FFmpeg 下载耗时较长，放到后台准备（进度见 list_background_tasks），不占用启动总超时
+                        if !is_dev_mode && resolve_ffmpeg_tool(&app_handle, "ffmpeg").is_err() {
+                            log_meta(&trace, "ready_timeout_ffmpeg_missing", 1);
+                            #[cfg(target_os = "windows")]
+                            {
+                                let root = resource_root.clone();
+                                let proxy = download_proxy_url(&app_handle);
+                                let progress = state.progress_tasks.clone();
+                                tauri::async_runtime::spawn(async move {
+                                    match ensure_ffmpeg_binaries(&root, proxy, &progress).await {
+                                        Ok(()) => app_log!(info, "[backend] 后台准备 FFmpeg 完成"),
+                                        Err(e) => {
+                                            app_log!(error, "[backend] 后台准备 FFmpeg 失败: {}", e)
+                                        }
+                                    }
+                                });
+                                err = format!(
+                                    "{}；后端可能因缺少 FFmpeg 未就绪，已在后台准备 FFmpeg，完成后请重启后端",
+                                    err
+                                );
+                            }
+                            #[cfg(not(target_os = "windows"))]
+                            {
+                                err = format!(
+                                    "{}；后端可能因缺少 FFmpeg 未就绪，请手动安装 FFmpeg 或设置 SACV_FFMPEG_DIR",
+                                    err
+                                );
+                            }
+                        }
+                        Err(BackendError::ReadyTimeout { message: err })
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            backend.backend_starting.store(false, Ordering::SeqCst);
+            *backend.backend_port.lock().unwrap() = 0;
+            *backend.backend_boot_token.lock().unwrap() = None;
+            append_log_line(backend_log_path(), &format!("[error] spawn_failed: {}", e));
+            let dll_hint = if use_packaged_backend {
+                diagnose_backend_dlls(&backend_executable)
+            } else {
+                None
+            };
+            let message = match dll_hint {
+                Some(hint) => format!("启动后端失败: {}；{}", e, hint),
+                None => format!("启动后端失败: {}", e),
+            };
+            Err(BackendError::SpawnFailed { message })
+        }
+    }
+}
+
+// 监控后端子进程：意外退出时清理状态并发射 backend-exited {code, expected:false}；
+// 子进程被 stop_backend 等主动取走时发射 expected:true（此时退出码无意义，为 null）
+const CRASH_SNAPSHOT_TAIL_LINES: usize = 200;
+const MAX_CRASH_SNAPSHOTS: usize = 5;
+
+// 崩溃快照目录（应用日志目录），按文件名排序即为时间顺序
+fn crash_snapshot_files(app_handle: &AppHandle) -> Vec<PathBuf> {
+    let dir = match app_handle.path().app_log_dir() {
+        Ok(d) => d,
+        Err(_) => return Vec::new(),
+    };
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .map(|rd| {
+            rd.filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.file_name()
+                        .map(|n| n.to_string_lossy())
+                        .map_or(false, |n| {
+                            n.starts_with("backend_crash_") && n.ends_with(".log")
+                        })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+// 后端非预期退出时保存崩溃快照：退出码、时间戳与退出前最近的 stderr，只保留最近 MAX_CRASH_SNAPSHOTS 份
+fn write_crash_snapshot(
+    app_handle: &AppHandle,
+    profile: &str,
+    pid: u32,
+    exit_code: Option<i32>,
+    stderr_tail: &[String],
+) -> Result<PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("无法获取日志目录: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("创建日志目录失败 {:?}: {}", dir, e))?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let path = dir.join(format!("backend_crash_{}.log", now.as_millis()));
+    let mut content = format!(
+        "timestamp={}\nprofile={}\npid={}\nexit_code={:?}\n--- stderr (last {} lines) ---\n",
+        now.as_secs(),
+        profile,
+        pid,
+        exit_code,
+        stderr_tail.len()
+    );
+    for line in stderr_tail {
+        content.push_str(line);
+        content.push('\n');
+    }
+    std::fs::write(&path, content).map_err(|e| format!("写入崩溃快照失败 {:?}: {}", path, e))?;
+    let files = crash_snapshot_files(app_handle);
+    if files.len() > MAX_CRASH_SNAPSHOTS {
+        for old in &files[..files.len() - MAX_CRASH_SNAPSHOTS] {
+            let _ = std::fs::remove_file(old);
+        }
+    }
+    Ok(path)
+}
+
+fn spawn_backend_exit_monitor(app_handle: &AppHandle, backend: &BackendInstance, pid: u32) {
+    let app = app_handle.clone();
+    let instance = backend.clone();
+    let profile = backend.profile.clone();
+    let stderr_tail = backend.stderr_tail.clone();
+    let process = backend.backend_process.clone();
+    let port = backend.backend_port.clone();
+    let boot_token = backend.backend_boot_token.clone();
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_secs(1));
+        let mut guard = process.lock().unwrap();
+        let exit_code = match guard.as_mut() {
+            Some(child) if child.id() == pid => match child.try_wait() {
+                Ok(Some(status)) => status.code(),
+                Ok(None) => continue,
+                Err(_) => None,
+            },
+            _ => {
+                // 进程已被替换（重启）时由新的启动流程维护状态
+                let replaced = guard.is_some();
+                drop(guard);
+                if !replaced && instance.lifecycle() == BackendLifecycle::Ready {
+                    instance.set_lifecycle(&app, BackendLifecycle::Stopped);
+                }
+                let _ = app.emit(
+                    "backend-exited",
+                    serde_json::json!({ "profile": profile, "pid": pid, "code": null, "expected": true }),
+                );
+                return;
+            }
+        };
+        *guard = None;
+        drop(guard);
+        *port.lock().unwrap() = 0;
+        *boot_token.lock().unwrap() = None;
+        *instance.server_info.lock().unwrap() = None;
+        append_log_line(
+            backend_log_path(),
+            &format!("[error] backend_exited pid={} code={:?}", pid, exit_code),
+        );
+        // 仅在已就绪后退出才算运行期崩溃：启动（或重连）过程中的退出由进行中的启动流程汇报并维护状态，
+        // 不能在此再触发自动重连，否则启动即崩溃的后端会被反复拉起
+        let was_ready = instance.lifecycle() == BackendLifecycle::Ready;
+        if was_ready {
+            instance.set_lifecycle(&app, BackendLifecycle::Crashed);
+        }
+        // 等 stderr 读取线程读完管道中剩余的输出后再取尾部
+        instance.reap_log_threads(Duration::from_secs(1));
+        let tail: Vec<String> = stderr_tail.lock().unwrap().iter().cloned().collect();
+        match write_crash_snapshot(&app, &profile, pid, exit_code, &tail) {
+            Ok(path) => append_log_line(
+                backend_log_path(),
+                &format!("[meta] crash_snapshot={}", path.to_string_lossy()),
+            ),
+            Err(e) => app_log!(error, "[backend] {}", e),
+        }
+        let _ = app.emit(
+            "backend-exited",
+            serde_json::json!({ "profile": profile, "pid": pid, "code": exit_code, "expected": false }),
+        );
+        if was_ready
+            && auto_reconnect_enabled(&app)
+            && !app
+                .state::<AppState>()
+                .app_is_quitting
+                .load(Ordering::SeqCst)
+        {
+            tauri::async_runtime::spawn(reconnect_backend(app.clone(), instance.clone()));
+        }
+        return;
+    });
+}
+
+// 停止指定 profile 持有的后端子进程（供命令与退出清理共用）
+fn stop_backend_process(state: &AppState, profile: Option<&str>) -> Result<bool, String> {
+    // 不存在的 profile 没有可停止的进程
+    let Ok(backend) = state.existing_backend(profile) else {
+        return Ok(false);
+    };
+    // 按进程名兜底清理会误杀其它 profile 的后端，仅在没有其它实例运行时执行
+    #[cfg(target_os = "windows")]
+    let others_running = state
+        .other_backends(&backend.profile)
+        .iter()
+        .any(|b| b.backend_process.lock().unwrap().is_some());
+    let mut process_guard = backend.backend_process.lock().unwrap();
+
+    if let Some(mut child) = process_guard.take() {
+        let pid = child.id();
+        #[cfg(target_os = "windows")]
+        if let Some(job) = backend.backend_job.lock().unwrap().take() {
+            terminate_backend_job(job);
+        }
+        #[cfg(unix)]
+        let kill_result = terminate_process_group(&mut child);
+        #[cfg(not(unix))]
+        let kill_result = child.kill();
+        match kill_result {
+            Ok(_) => {
+                let _ = child.wait(); // 等待进程完全退出
+                drop(process_guard);
+                // 进程退出后管道关闭，日志读取线程读到 EOF 即退出
+                backend.reap_log_threads(Duration::from_secs(1));
+                *backend.backend_port.lock().unwrap() = 0;
+                *backend.backend_boot_token.lock().unwrap() = None;
+                *backend.server_info.lock().unwrap() = None;
+                app_log!(info, "[backend] 已停止 (pid={})", pid);
+                #[cfg(target_os = "windows")]
+                if !others_running {
+                    // 额外兜底：强制结束所有同名后端进程，避免残留
+                    kill_all_backend_processes();
+                }
+                Ok(true)
+            }
+            Err(e) => Err(format!("停止后端失败: {}", e)),
+        }
+    } else {
+        #[cfg(target_os = "windows")]
+        if !others_running {
+            // 无记录的子进程，但可能仍有残留后端，兜底清理
+            kill_all_backend_processes();
+        }
+        Ok(false) // 没有运行的进程
+    }
+}
+
+// 在阻塞线程池中停止后端：Unix 下 SIGTERM 后最多等待 3 秒再 SIGKILL，不能占用异步运行时的工作线程
+async fn stop_backend_process_async(
+    app_handle: &AppHandle,
+    profile: Option<String>,
+) -> Result<bool, String> {
+    let app = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        stop_backend_process(&app.state::<AppState>(), profile.as_deref())
+    })
+    .await
+    .map_err(|e| format!("停止后端任务异常: {}", e))?
+}
+
+// Tauri命令：修复后端——先停止后端，删除解压目录与 .backend_zip_stamp 后重新解压（仅 Windows 打包版使用压缩包）
+#[tauri::command]
+async fn force_reextract_backend(app_handle: AppHandle) -> Result<(), String> {
+    // 所有 profile 共用同一份解压目录，需全部停止
+    let state = app_handle.state::<AppState>();
+    for backend in state.all_backends() {
+        stop_backend_process_async(&app_handle, Some(backend.profile.clone())).await?;
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let (_, resource_root) = backend_resource_dirs(&app_handle);
+        if !resource_root.join("superAutoCutVideoBackend.zip").exists() {
+            return Err("未找到后端压缩包 superAutoCutVideoBackend.zip，无法重新解压".to_string());
+        }
+        let app_data_dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("无法获取应用数据目录: {}", e))?;
+        let extracted_backend_dir = app_data_dir.join("superAutoCutVideoBackend");
+        if extracted_backend_dir.exists() {
+            std::fs::remove_dir_all(&extracted_backend_dir)
+                .map_err(|e| format!("删除解压目录失败 {:?}: {}", extracted_backend_dir, e))?;
+        }
+        // 可执行文件路径缓存可能指向已删除的目录，一并清掉
+        if let Ok(cache_dir) = app_handle.path().app_cache_dir() {
+            let _ = std::fs::remove_file(cache_dir.join(BACKEND_EXE_CACHE_FILE));
+        }
+        let app_clone = app_handle.clone();
+        let exe = tauri::async_runtime::spawn_blocking(move || {
+            ensure_backend_executable_available(&app_clone, &resource_root)
+        })
+        .await
+        .map_err(|e| format!("解压后端任务异常: {}", e))??;
+        append_log_line(
+            backend_log_path(),
+            &format!(
+                "[meta] force_reextract_backend ok: {}",
+                exe.to_string_lossy()
+            ),
+        );
+        Ok(())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("当前平台的后端无需解压".to_string())
+    }
+}
+
+// Tauri命令：手动指定后端端口（0 表示清除覆盖），下次启动/restart_backend 时生效
+#[tauri::command]
+async fn set_backend_port(
+    state: State<'_, AppState>,
+    port: u16,
+    profile: Option<String>,
+) -> Result<(), String> {
+    validate_profile(&profile)?;
+    let backend = state.backend(profile.as_deref());
+    if port == 0 {
+        *backend.port_override.lock().unwrap() = None;
+        return Ok(());
+    }
+    if port < 1024 {
+        return Err(format!("端口 {} 无效，请使用 1024-65535 之间的端口", port));
+    }
+    let current = *backend.backend_port.lock().unwrap();
+    if port != current && !is_port_available(port) {
+        return Err(format!("端口 {} 已被占用", port));
+    }
+    *backend.port_override.lock().unwrap() = Some(port);
+    Ok(())
+}
+
+// Tauri命令：重启后端（先停止当前子进程，再按当前配置重新启动）
+#[tauri::command]
+async fn restart_backend(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    profile: Option<String>,
+) -> Result<BackendStatus, BackendError> {
+    validate_profile(&profile)?;
+    stop_backend_process_async(&app_handle, profile.clone()).await?;
+    start_backend(state, app_handle, profile).await
+}
+
+// Tauri命令：停止Python后端（profile 为空时停止默认实例）
+#[tauri::command]
+async fn stop_backend(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    profile: Option<String>,
+) -> Result<bool, String> {
+    validate_profile(&profile)?;
+    // 用户主动停止时不再自动重连
+    state
+        .existing_backend(profile.as_deref())?
+        .reconnect_cancel
+        .store(true, Ordering::SeqCst);
+    stop_backend_process_async(&app_handle, profile).await
+}
+
+// Tauri命令：结束所有残留的后端进程，返回结束的进程数
+#[tauri::command]
+async fn kill_orphan_backends(state: State<'_, AppState>) -> Result<u32, String> {
+    // 本应用各 profile 正在管理的后端不是残留进程，排除在外（需要停止时走 stop_backend）
+    let owned_pids: Vec<u32> = state
+        .all_backends()
+        .iter()
+        .filter_map(|b| b.backend_process.lock().unwrap().as_ref().map(|c| c.id()))
+        .collect();
+    let mut killed = 0u32;
+    for pid in list_backend_pids() {
+        if owned_pids.contains(&pid) {
+            continue;
+        }
+        if kill_process_tree(pid) {
+            killed += 1;
+        }
+    }
+    app_log!(info, "[backend] 已清理残留后端进程: {}", killed);
+    Ok(killed)
+}
+
+// 应用退出时的清理：通知后台下载/解压中止并等待其清理临时文件（带超时），再停止后端
+fn cleanup_app(app: &AppHandle) {
+    app_log!(info, "[app] 退出清理");
+    BACKGROUND_CANCEL.store(true, Ordering::SeqCst);
+    let deadline = std::time::Instant::now() + Duration::from_secs(10);
+    while BACKGROUND_TASKS.load(Ordering::SeqCst) > 0 && std::time::Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(100));
+    }
+    let state = app.state::<AppState>();
+    for backend in state.all_backends() {
+        let _ = stop_backend_process(&state, Some(&backend.profile));
+    }
+}
+
+// Tauri命令：重启整个应用。先执行退出清理停掉本应用拉起的后端，并等待其端口释放，
+// 避免新实例启动时发现并复用到尚未退出的旧后端
+#[tauri::command]
+async fn restart_app(app_handle: AppHandle) -> Result<(), String> {
+    app_log!(info, "[app] 重启应用");
+    let state = app_handle.state::<AppState>();
+    state.app_is_quitting.store(true, Ordering::SeqCst);
+    state.quit_confirmed.store(true, Ordering::SeqCst);
+    let owned_ports: Vec<u16> = state
+        .all_backends()
+        .iter()
+        .filter(|b| b.backend_process.lock().unwrap().is_some())
+        .map(|b| *b.backend_port.lock().unwrap())
+        .filter(|p| *p != 0)
+        .collect();
+    let app = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || cleanup_app(&app))
+        .await
+        .map_err(|e| format!("重启前清理失败: {}", e))?;
+    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+    while owned_ports.iter().any(|p| !is_port_available(*p)) {
+        if std::time::Instant::now() >= deadline {
+            app_log!(warn, "[app] 重启前等待后端端口释放超时: {:?}", owned_ports);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    app_handle.restart()
+}
+
+// Tauri命令：获取后端状态（profile 为空时为默认实例）
+#[tauri::command]
+async fn get_backend_status(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<BackendStatus, String> {
+    validate_profile(&profile)?;
+    let backend = state.existing_backend(profile.as_deref())?;
+    let mut process_guard = backend.backend_process.lock().unwrap();
+
+    if let Some(ref mut child) = *process_guard {
+        match child.try_wait() {
+            Ok(Some(_)) => {
+                // 进程已退出
+                *process_guard = None;
+                Ok(BackendStatus {
+                    running: false,
+                    port: 0,
+                    pid: None,
                     boot_token: None,
+                    source: "none".to_string(),
+                    ready_ms: None,
+                    server_info: None,
+                })
+            }
+            Ok(None) => {
+                // 进程仍在运行
+                let port = *backend.backend_port.lock().unwrap();
+                Ok(BackendStatus {
+                    running: true,
+                    port,
+                    pid: Some(child.id()),
+                    boot_token: backend.backend_boot_token.lock().unwrap().clone(),
+                    source: backend.backend_source.lock().unwrap().clone(),
+                    ready_ms: None,
+                    server_info: backend.server_info.lock().unwrap().clone(),
                 })
             }
-            Ok(None) => {
-                // 进程仍在运行
-                let port = *state.backend_port.lock().unwrap();
-                Ok(BackendStatus {
-                    running: true,
-                    port,
-                    pid: Some(child.id()),
-                    boot_token: state.backend_boot_token.lock().unwrap().clone(),
-                })
+            Err(e) => Err(format!("检查进程状态失败: {}", e)),
+        }
+    } else {
+        Ok(BackendStatus {
+            running: false,
+            port: 0,
+            pid: None,
+            boot_token: None,
+            source: "none".to_string(),
+            ready_ms: None,
+            server_info: None,
+        })
+    }
+}
+
+const RECONNECT_MAX_ATTEMPTS: u32 = 5;
+const RECONNECT_BASE_DELAY_MS: u64 = 2000;
+const RECONNECT_MAX_DELAY_MS: u64 = 30000;
+
+// 后端意外退出后是否自动重连（配置 auto_reconnect，默认开启）
+fn auto_reconnect_enabled(app: &AppHandle) -> bool {
+    load_app_config(app)
+        .get("auto_reconnect")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+// 后端意外退出后按指数退避重启（2s 起翻倍，最长 30s，最多 5 次）；每次等待前发射 backend-reconnecting {attempt, next_retry_in_ms}，
+// 期间生命周期保持 reconnecting，成功后由 start_backend 置为 ready，放弃或耗尽次数后置为 crashed 并发射 backend-reconnect-failed
+async fn reconnect_backend(app_handle: AppHandle, backend: BackendInstance) {
+    if backend.reconnect_active.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    backend.reconnect_cancel.store(false, Ordering::SeqCst);
+    backend.set_lifecycle(&app_handle, BackendLifecycle::Reconnecting);
+    let cancelled = || {
+        backend.reconnect_cancel.load(Ordering::SeqCst)
+            || background_cancelled()
+            || app_handle
+                .state::<AppState>()
+                .app_is_quitting
+                .load(Ordering::SeqCst)
+    };
+    let mut delay_ms = RECONNECT_BASE_DELAY_MS;
+    let mut recovered = false;
+    for attempt in 1..=RECONNECT_MAX_ATTEMPTS {
+        let _ = app_handle.emit(
+            "backend-reconnecting",
+            serde_json::json!({
+                "profile": backend.profile,
+                "attempt": attempt,
+                "max_attempts": RECONNECT_MAX_ATTEMPTS,
+                "next_retry_in_ms": delay_ms,
+            }),
+        );
+        // 分段等待，便于及时响应取消与应用退出
+        let deadline = std::time::Instant::now() + Duration::from_millis(delay_ms);
+        while std::time::Instant::now() < deadline && !cancelled() {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        if cancelled() {
+            break;
+        }
+        app_log!(
+            info,
+            "[backend] 第 {} 次自动重连 (profile={})",
+            attempt,
+            backend.profile
+        );
+        let profile = Some(backend.profile.clone());
+        match start_backend(app_handle.state::<AppState>(), app_handle.clone(), profile).await {
+            Ok(status) if status.running => {
+                recovered = true;
+                break;
+            }
+            Ok(_) => {}
+            Err(e) => app_log!(warn, "[backend] 第 {} 次自动重连失败: {}", attempt, e),
+        }
+        delay_ms = (delay_ms * 2).min(RECONNECT_MAX_DELAY_MS);
+    }
+    backend.reconnect_active.store(false, Ordering::SeqCst);
+    if !recovered {
+        let user_cancelled = backend.reconnect_cancel.load(Ordering::SeqCst);
+        backend.set_lifecycle(&app_handle, BackendLifecycle::Crashed);
+        let _ = app_handle.emit(
+            "backend-reconnect-failed",
+            serde_json::json!({ "profile": backend.profile, "cancelled": user_cancelled }),
+        );
+    }
+}
+
+// Tauri命令：放弃进行中的自动重连，返回是否有重连被取消
+#[tauri::command]
+async fn cancel_reconnect(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<bool, String> {
+    validate_profile(&profile)?;
+    let backend = state.existing_backend(profile.as_deref())?;
+    if !backend.reconnect_active.load(Ordering::SeqCst) {
+        return Ok(false);
+    }
+    backend.reconnect_cancel.store(true, Ordering::SeqCst);
+    Ok(true)
+}
+
+// Tauri命令：获取后端生命周期状态（stopped/starting/ready/crashed/reconnecting），前端可订阅 backend-state-changed
+#[tauri::command]
+async fn get_backend_state(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<BackendLifecycle, String> {
+    validate_profile(&profile)?;
+    Ok(state.existing_backend(profile.as_deref())?.lifecycle())
+}
+
+// Tauri命令：直接请求当前端口的健康检查端点判断后端真实健康状态（不依赖进程句柄）
+#[tauri::command]
+async fn ping_backend(state: State<'_, AppState>, profile: Option<String>) -> Result<bool, String> {
+    validate_profile(&profile)?;
+    let backend = state.existing_backend(profile.as_deref())?;
+    let port = *backend.backend_port.lock().unwrap();
+    if port == 0 {
+        return Ok(false);
+    }
+    let client = backend_client_builder(&backend)
+        .timeout(Duration::from_millis(1500))
+        .build()
+        .map_err(|e| format!("创建请求客户端失败: {}", e))?;
+    Ok(
+        probe_health_endpoints(&client, "127.0.0.1", port, &backend_health_endpoints())
+            .await
+            .is_ok(),
+    )
+}
+
+// Tauri命令：返回前端直接访问后端所用的 base URL（含 scheme/host/port，不带结尾斜杠）
+// UDS 模式下后端不监听 TCP 端口，前端无法直接 fetch，返回错误提示改走命令代理
+#[tauri::command]
+async fn get_backend_base_url(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<String, String> {
+    validate_profile(&profile)?;
+    let backend = state.existing_backend(profile.as_deref())?;
+    if let Some(sock) = backend.backend_uds_path.lock().unwrap().as_ref() {
+        return Err(format!(
+            "后端当前通过 Unix socket 通信（{}），前端无法直接访问",
+            sock.to_string_lossy()
+        ));
+    }
+    let port = *backend.backend_port.lock().unwrap();
+    if port == 0 {
+        return Err("后端未运行".to_string());
+    }
+    Ok(format!("http://127.0.0.1:{}", port))
+}
+
+// Tauri命令：取消后端任务，返回后端确认的取消结果（响应中的 data，没有时返回整个响应）
+// 后端无响应时，若 interrupt_on_timeout 为 true，作为兜底停止整个后端进程（无法只中断单个任务），
+// 返回 status 为 backend_stopped；按主动停止处理，不触发自动重连
+#[tauri::command]
+async fn cancel_backend_task(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    task_id: String,
+    interrupt_on_timeout: Option<bool>,
+    profile: Option<String>,
+) -> Result<serde_json::Value, String> {
+    validate_profile(&profile)?;
+    let backend = state.existing_backend(profile.as_deref())?;
+    if task_id.is_empty()
+        || !task_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("任务 ID 不合法: {}", task_id));
+    }
+    let port = *backend.backend_port.lock().unwrap();
+    if port == 0 {
+        return Err("后端未运行".to_string());
+    }
+    let token = backend
+        .backend_boot_token
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_default();
+    let client = backend_client_builder(&backend)
+        .timeout(Duration::from_millis(5000))
+        .build()
+        .map_err(|e| format!("创建请求客户端失败: {}", e))?;
+    let url = format!("http://127.0.0.1:{}/api/tasks/{}/cancel", port, task_id);
+    match client
+        .post(&url)
+        .header("X-SACV-Boot-Token", token)
+        .send()
+        .await
+    {
+        Ok(resp) => {
+            let status = resp.status();
+            let v: serde_json::Value = resp.json().await.unwrap_or(serde_json::Value::Null);
+            if !status.is_success() {
+                return Err(format!("取消任务失败: HTTP {} {}", status.as_u16(), v));
+            }
+            Ok(v.get("data").cloned().unwrap_or(v))
+        }
+        Err(e) => {
+            let err = BackendProbeError::from(e);
+            if !interrupt_on_timeout.unwrap_or(false) {
+                return Err(format!("取消任务失败: 后端无响应（{}）", err));
+            }
+            if backend.backend_process.lock().unwrap().is_none() {
+                return Err(format!(
+                    "取消任务失败: 后端无响应（{}），且无可停止的后端进程",
+                    err
+                ));
+            }
+            backend.reconnect_cancel.store(true, Ordering::SeqCst);
+            stop_backend_process_async(&app_handle, profile.clone())
+                .await
+                .map_err(|e| {
+                    format!("取消任务失败: 后端无响应（{}），停止后端也失败: {}", err, e)
+                })?;
+            append_log_line(
+                backend_log_path(),
+                &format!(
+                    "[meta] cancel_task_backend_stopped profile={} task={}",
+                    backend.profile, task_id
+                ),
+            );
+            Ok(serde_json::json!({ "task_id": task_id, "status": "backend_stopped" }))
+        }
+    }
+}
+
+// 向后端发送 POST（带 boot_token，可选 JSON 请求体），仅关心是否返回 2xx
+async fn post_backend(
+    backend: &BackendInstance,
+    path: &str,
+    body: Option<serde_json::Value>,
+) -> Result<(), BackendProbeError> {
+    let port = *backend.backend_port.lock().unwrap();
+    if port == 0 {
+        return Err(BackendProbeError::ConnRefused);
+    }
+    let token = backend
+        .backend_boot_token
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_default();
+    let client = backend_client_builder(backend)
+        .timeout(Duration::from_millis(5000))
+        .build()
+        .map_err(|_| BackendProbeError::ConnRefused)?;
+    let mut req = client
+        .post(format!("http://127.0.0.1:{}{}", port, path))
+        .header("X-SACV-Boot-Token", token);
+    if let Some(body) = body {
+        req = req.json(&body);
+    }
+    let resp = req.send().await?;
+    if !resp.status().is_success() {
+        return Err(BackendProbeError::BadStatus(resp.status().as_u16()));
+    }
+    Ok(())
+}
+
+const IDLE_CHECK_INTERVAL_SECS: u64 = 30;
+
+// 空闲计时：配置 idle_timeout_secs > 0 时，超时后按 idle_action 挂起后端
+// sleep（默认）调用 /api/server/sleep 卸载模型，后端不支持时退化为 stop；stop 直接停止后端
+fn spawn_idle_monitor(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(IDLE_CHECK_INTERVAL_SECS)).await;
+            let state = app_handle.state::<AppState>();
+            if state.app_is_quitting.load(Ordering::SeqCst) || background_cancelled() {
+                return;
+            }
+            let config = load_app_config(&app_handle);
+            let timeout = config
+                .get("idle_timeout_secs")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            let idle = state.last_activity.lock().unwrap().elapsed();
+            if timeout == 0 || idle < Duration::from_secs(timeout) {
+                continue;
+            }
+            let action = config
+                .get("idle_action")
+                .and_then(|v| v.as_str())
+                .unwrap_or("sleep")
+                .to_string();
+            for backend in state.all_backends() {
+                if backend.backend_suspended.lock().unwrap().is_some()
+                    || backend.backend_process.lock().unwrap().is_none()
+                {
+                    continue;
+                }
+                let mode = if action == "sleep"
+                    && post_backend(&backend, "/api/server/sleep", None)
+                        .await
+                        .is_ok()
+                {
+                    "sleep"
+                } else {
+                    match stop_backend_process_async(&app_handle, Some(backend.profile.clone()))
+                        .await
+                    {
+                        Ok(_) => "stop",
+                        Err(e) => {
+                            app_log!(error, "[backend] 空闲挂起失败: {}", e);
+                            continue;
+                        }
+                    }
+                };
+                append_log_line(
+                    backend_log_path(),
+                    &format!(
+                        "[meta] backend_idle_suspended profile={} mode={} idle_secs={}",
+                        backend.profile,
+                        mode,
+                        idle.as_secs()
+                    ),
+                );
+                *backend.backend_suspended.lock().unwrap() = Some(mode.to_string());
+                let _ = app_handle.emit(
+                    "backend-idle-suspended",
+                    serde_json::json!({ "profile": backend.profile, "mode": mode }),
+                );
+            }
+        }
+    });
+}
+
+// Tauri命令：前端上报用户活动（心跳）；后端处于空闲挂起状态时透明唤醒，返回是否执行了唤醒
+#[tauri::command]
+async fn report_activity(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<bool, String> {
+    *state.last_activity.lock().unwrap() = std::time::Instant::now();
+    let mut woke = false;
+    for backend in state.all_backends() {
+        let suspended = backend.backend_suspended.lock().unwrap().take();
+        let mode = match suspended {
+            Some(m) => m,
+            None => continue,
+        };
+        backend.set_lifecycle(&app_handle, BackendLifecycle::Reconnecting);
+        // sleep 模式优先调用 /api/server/wake；失败（如后端已退出）时重新启动后端
+        if mode == "sleep"
+            && post_backend(&backend, "/api/server/wake", None)
+                .await
+                .is_ok()
+        {
+            backend.set_lifecycle(&app_handle, BackendLifecycle::Ready);
+        } else {
+            let profile = Some(backend.profile.clone());
+            if let Err(e) =
+                start_backend(app_handle.state::<AppState>(), app_handle.clone(), profile).await
+            {
+                return Err(format!("唤醒后端失败: {}", e));
+            }
+        }
+        let _ = app_handle.emit(
+            "backend-idle-resumed",
+            serde_json::json!({ "profile": backend.profile, "mode": mode }),
+        );
+        woke = true;
+    }
+    Ok(woke)
+}
+
+// Tauri命令：设置空闲挂起策略（timeout_secs 为 0 表示关闭；action 为 sleep 或 stop）
+#[tauri::command]
+async fn set_idle_policy(
+    app_handle: AppHandle,
+    timeout_secs: u64,
+    action: Option<String>,
+) -> Result<(), String> {
+    if let Some(a) = action.as_deref() {
+        if a != "sleep" && a != "stop" {
+            return Err(format!("不支持的空闲挂起方式: {}", a));
+        }
+    }
+    update_app_config(&app_handle, |config| {
+        config.insert(
+            "idle_timeout_secs".to_string(),
+            serde_json::json!(timeout_secs),
+        );
+        if let Some(a) = action {
+            config.insert("idle_action".to_string(), serde_json::json!(a));
+        }
+    })
+}
+
+const BACKEND_LOG_LEVELS: [&str; 5] = ["debug", "info", "warning", "error", "critical"];
+
+// Tauri命令：运行时调整后端日志级别并记入配置 backend_log_level（下次启动通过 SACV_LOG_LEVEL 传入）
+// 后端未运行时仅保存配置；后端不支持热调时返回提示，可配合 restart_backend 生效
+#[tauri::command]
+async fn set_backend_log_level(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    level: String,
+    profile: Option<String>,
+) -> Result<(), String> {
+    validate_profile(&profile)?;
+    let level = level.trim().to_lowercase();
+    if !BACKEND_LOG_LEVELS.contains(&level.as_str()) {
+        return Err(format!("不支持的日志级别: {}", level));
+    }
+    let backend = state.existing_backend(profile.as_deref())?;
+    update_app_config(&app_handle, |config| {
+        config.insert("backend_log_level".to_string(), serde_json::json!(level));
+    })?;
+    if *backend.backend_port.lock().unwrap() == 0 {
+        return Ok(());
+    }
+    match post_backend(
+        &backend,
+        "/api/server/log-level",
+        Some(serde_json::json!({ "level": level })),
+    )
+    .await
+    {
+        Ok(()) => {
+            append_log_line(
+                backend_log_path(),
+                &format!(
+                    "[meta] backend_log_level profile={} level={}",
+                    backend.profile, level
+                ),
+            );
+            Ok(())
+        }
+        Err(BackendProbeError::BadStatus(404)) | Err(BackendProbeError::BadStatus(405)) => {
+            Err("后端不支持运行时调整日志级别，需重启后端生效".to_string())
+        }
+        Err(e) => Err(format!("调整后端日志级别失败: {}", e)),
+    }
+}
+
+// 应用语言：配置 language 优先，其次系统 locale，都取不到时默认 zh-CN；启动后端时通过 SACV_LANG 传入
+fn app_language(app: &AppHandle) -> String {
+    load_app_config(app)
+        .get("language")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .filter(|s| is_valid_language_tag(s))
+        .or_else(|| tauri_plugin_os::locale().filter(|s| is_valid_language_tag(s)))
+        .unwrap_or_else(|| "zh-CN".to_string())
+}
+
+fn is_valid_language_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag.len() <= 35
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// Tauri命令：切换应用语言并记入配置 language，同时通知运行中的后端（/api/server/set-lang）；
+// 后端不支持热切换时返回提示，可配合 restart_backend 生效
+#[tauri::command]
+async fn set_app_language(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    lang: String,
+) -> Result<(), String> {
+    let lang = lang.trim().to_string();
+    if !is_valid_language_tag(&lang) {
+        return Err(format!("语言标识不合法: {}", lang));
+    }
+    update_app_config(&app_handle, |config| {
+        config.insert("language".to_string(), serde_json::json!(lang));
+    })?;
+    let mut unsupported = false;
+    for backend in state.all_backends() {
+        if *backend.backend_port.lock().unwrap() == 0 {
+            continue;
+        }
+        match post_backend(
+            &backend,
+            "/api/server/set-lang",
+            Some(serde_json::json!({ "lang": lang })),
+        )
+        .await
+        {
+            Ok(()) => append_log_line(
+                backend_log_path(),
+                &format!(
+                    "[meta] backend_lang profile={} lang={}",
+                    backend.profile, lang
+                ),
+            ),
+            Err(BackendProbeError::BadStatus(404)) | Err(BackendProbeError::BadStatus(405)) => {
+                unsupported = true
+            }
+            Err(e) => return Err(format!("切换后端语言失败: {}", e)),
+        }
+    }
+    if unsupported {
+        return Err("后端不支持运行时切换语言，需重启后端生效".to_string());
+    }
+    Ok(())
+}
+
+// 统计各后端运行中的任务数（/api/server/info 的 data.running_tasks）；后端未运行或无响应时按 0 计，
+// 有响应但缺少该字段（旧版后端）时记录日志后同样按 0 计
+async fn running_backend_task_count(state: &AppState) -> u64 {
+    let mut total = 0;
+    for backend in state.all_backends() {
+        let port = *backend.backend_port.lock().unwrap();
+        if port == 0 {
+            continue;
+        }
+        let client = match backend_client_builder(&backend)
+            .timeout(Duration::from_millis(1500))
+            .build()
+        {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        let url = format!("http://127.0.0.1:{}/api/server/info", port);
+        let info = match client.get(&url).send().await {
+            Ok(resp) => resp.json::<serde_json::Value>().await.ok(),
+            Err(_) => continue,
+        };
+        match info
+            .as_ref()
+            .and_then(|v| v.pointer("/data/running_tasks"))
+            .and_then(|n| n.as_u64())
+        {
+            Some(n) => total += n,
+            None => app_log!(
+                warn,
+                "[backend] /api/server/info 未返回 running_tasks，无法判断是否有运行中任务 (profile={})",
+                backend.profile
+            ),
+        }
+    }
+    total
+}
+
+// Tauri命令：用户在确认框中选择仍要退出（有运行中任务时由 quit-confirm-requested 事件触发）
+#[tauri::command]
+async fn confirm_app_quit(app: AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    state.app_is_quitting.store(true, Ordering::SeqCst);
+    state.quit_confirmed.store(true, Ordering::SeqCst);
+    if let Some(window) = app.get_webview_window("main") {
+        window.close().map_err(|e| format!("关闭窗口失败: {}", e))
+    } else {
+        app.exit(0);
+        Ok(())
+    }
+}
+
+// Tauri命令：透传后端自报的 /api/server/info 元数据（返回其中的 data）
+#[tauri::command]
+async fn get_server_info(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<serde_json::Value, String> {
+    validate_profile(&profile)?;
+    fetch_backend_info(&state.existing_backend(profile.as_deref())?).await
+}
+
+// 请求后端 /api/server/info 的 data，成功时更新实例缓存
+async fn fetch_backend_info(backend: &BackendInstance) -> Result<serde_json::Value, String> {
+    let port = *backend.backend_port.lock().unwrap();
+    if port == 0 {
+        return Err("后端未运行".to_string());
+    }
+    let client = backend_client_builder(backend)
+        .timeout(Duration::from_millis(3000))
+        .build()
+        .map_err(|e| format!("创建请求客户端失败: {}", e))?;
+    let url = format!("http://127.0.0.1:{}/api/server/info", port);
+    let resp = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("请求后端信息失败: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("请求后端信息返回状态异常: {}", resp.status()));
+    }
+    let v: serde_json::Value = resp
+        .json()
+        .await
+        .map_err(|e| format!("解析后端信息失败: {}", e))?;
+    let data = v
+        .get("data")
+        .cloned()
+        .ok_or_else(|| "后端信息缺少 data 字段".to_string())?;
+    *backend.server_info.lock().unwrap() = Some(data.clone());
+    Ok(data)
+}
+
+const BACKEND_REQUEST_DEFAULT_TIMEOUT_MS: u64 = 30_000;
+const BACKEND_REQUEST_MAX_TIMEOUT_MS: u64 = 600_000;
+
+// 代理请求的响应：状态码与响应体（JSON 解析失败时为原始文本）
+#[derive(Serialize)]
+struct BackendProxyResponse {
+    status: u16,
+    body: serde_json::Value,
+}
+
+// Tauri命令：代理前端到后端的 HTTP 请求（带 boot_token）。传入 request_id 时可用 cancel_backend_request 中止，
+// 请求在独立任务中执行，中止即 drop 其 future 并断开连接
+#[tauri::command]
+async fn backend_request(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+    method: String,
+    path: String,
+    body: Option<serde_json::Value>,
+    request_id: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<BackendProxyResponse, String> {
+    validate_profile(&profile)?;
+    let method = reqwest::Method::from_bytes(method.trim().to_ascii_uppercase().as_bytes())
+        .ok()
+        .filter(|m| {
+            [
+                reqwest::Method::GET,
+                reqwest::Method::POST,
+                reqwest::Method::PUT,
+                reqwest::Method::PATCH,
+                reqwest::Method::DELETE,
+            ]
+            .contains(m)
+        })
+        .ok_or_else(|| format!("不支持的请求方法: {}", method))?;
+    if !path.starts_with('/') || path.starts_with("//") {
+        return Err(format!("请求路径不合法: {}", path));
+    }
+    let request_id = request_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    let backend = state.existing_backend(profile.as_deref())?;
+    let port = *backend.backend_port.lock().unwrap();
+    if port == 0 {
+        return Err("后端未运行".to_string());
+    }
+    let token = backend
+        .backend_boot_token
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_default();
+    let timeout = timeout_ms
+        .unwrap_or(BACKEND_REQUEST_DEFAULT_TIMEOUT_MS)
+        .clamp(1, BACKEND_REQUEST_MAX_TIMEOUT_MS);
+    let client = backend_client_builder(&backend)
+        .timeout(Duration::from_millis(timeout))
+        .build()
+        .map_err(|e| format!("创建请求客户端失败: {}", e))?;
+    let mut req = client
+        .request(method, format!("http://127.0.0.1:{}{}", port, path))
+        .header("X-SACV-Boot-Token", token);
+    if let Some(body) = body {
+        req = req.json(&body);
+    }
+    // 持锁完成查重、派发与登记，重复 id 在发出请求前即被拒绝，取消也不会错过刚派发的任务
+    let handle = {
+        let mut requests = state.backend_requests.lock().unwrap();
+        if let Some(id) = &request_id {
+            if requests.contains_key(id) {
+                return Err(format!("请求 id 已在使用: {}", id));
+            }
+        }
+        let handle = tauri::async_runtime::spawn(async move {
+            let resp = req
+                .send()
+                .await
+                .map_err(|e| format!("请求后端失败: {}", e))?;
+            let status = resp.status().as_u16();
+            let text = resp
+                .text()
+                .await
+                .map_err(|e| format!("读取后端响应失败: {}", e))?;
+            let body = serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text));
+            Ok::<_, String>(BackendProxyResponse { status, body })
+        });
+        if let Some(id) = &request_id {
+            requests.insert(id.clone(), handle.inner().abort_handle());
+        }
+        handle
+    };
+    let result = handle.await;
+    // 映射中的条目已被 cancel_backend_request 取走即视为取消
+    let cancelled = request_id.as_ref().map_or(false, |id| {
+        state.backend_requests.lock().unwrap().remove(id).is_none()
+    });
+    match result {
+        Ok(r) => r,
+        Err(_) if cancelled => Err("请求已取消".to_string()),
+        Err(e) => Err(format!("后端请求任务异常: {}", e)),
+    }
+}
+
+// Tauri命令：取消 backend_request 发起的进行中请求，返回是否找到该请求
+#[tauri::command]
+async fn cancel_backend_request(state: State<'_, AppState>, id: String) -> Result<bool, String> {
+    match state.backend_requests.lock().unwrap().remove(id.trim()) {
+        Some(handle) => {
+            handle.abort();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+// Tauri命令：列出后台下载/解压任务（进行中，以及 60 秒内结束的任务），按开始时间排序
+#[tauri::command]
+async fn list_background_tasks(state: State<'_, AppState>) -> Result<Vec<ProgressState>, String> {
+    let mut tasks = state.progress_tasks.lock().unwrap();
+    prune_progress_tasks(&mut tasks);
+    let mut list: Vec<ProgressState> = tasks.values().cloned().collect();
+    list.sort_by_key(|t| t.started_at_ms);
+    Ok(list)
+}
+
+// 环境变量名看起来携带凭据时需脱敏（boot_token、profile env 中的密钥等）
+fn is_sensitive_env_key(key: &str) -> bool {
+    let upper = key.to_ascii_uppercase();
+    ["TOKEN", "SECRET", "PASSWORD", "API_KEY", "APIKEY"]
+        .iter()
+        .any(|s| upper.contains(s))
+}
+
+// 记录本次注入后端的环境变量：写入后端日志并保存快照供 get_backend_launch_env 查询
+fn record_launch_env(backend: &BackendInstance, launch_env: &[(String, String)]) {
+    let snapshot: std::collections::BTreeMap<String, String> = launch_env
+        .iter()
+        .map(|(k, v)| {
+            let v = if is_sensitive_env_key(k) && !v.is_empty() {
+                "***".to_string()
+            } else {
+                v.clone()
+            };
+            (k.clone(), v)
+        })
+        .collect();
+    for (k, v) in &snapshot {
+        append_log_line(
+            backend_log_path(),
+            &format!("[meta] launch_env {}={}", k, v),
+        );
+    }
+    *backend.launch_env.lock().unwrap() = snapshot;
+}
+
+// Tauri命令：获取上次启动后端时注入的环境变量快照（敏感值已脱敏），排查 PATH/临时目录等问题
+#[tauri::command]
+async fn get_backend_launch_env(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<std::collections::BTreeMap<String, String>, String> {
+    validate_profile(&profile)?;
+    Ok(state
+        .existing_backend(profile.as_deref())?
+        .launch_env
+        .lock()
+        .unwrap()
+        .clone())
+}
+
+// Tauri命令：获取最近一次后端启动的决策链元数据
+#[tauri::command]
+async fn get_last_boot_trace(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<HashMap<String, String>, String> {
+    validate_profile(&profile)?;
+    Ok(state
+        .existing_backend(profile.as_deref())?
+        .backend_boot_trace
+        .lock()
+        .unwrap()
+        .clone())
+}
+
+// Tauri命令：设置产物输出根目录（下次启动后端时通过 SACV_OUTPUT_ROOT 传入）
+#[tauri::command]
+async fn set_output_root(state: State<'_, AppState>, path: Option<String>) -> Result<(), String> {
+    let path = path.filter(|p| !p.trim().is_empty());
+    if let Some(ref p) = path {
+        ensure_dir_writable(std::path::Path::new(p))?;
+    }
+    *state.output_root.lock().unwrap() = path;
+    Ok(())
+}
+
+// Tauri命令：获取当前产物输出根目录
+#[tauri::command]
+async fn get_output_root(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    Ok(state.output_root.lock().unwrap().clone())
+}
+
+// 定位 ffmpeg/ffprobe：依次查找 SACV_FFMPEG_DIR、资源目录（Windows 自动准备的位置）、系统 PATH
+fn resolve_ffmpeg_tool(app_handle: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    let file_name = if cfg!(target_os = "windows") {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = offline_ffmpeg_dir() {
+        dirs.push(dir);
+    }
+    if let Ok(resource_dir) = app_handle.path().resource_dir() {
+        dirs.push(resource_dir.join("resources"));
+        dirs.push(resource_dir);
+    }
+    for dir in dirs {
+        let candidate = dir.join(&file_name);
+        if candidate.is_file() {
+            return Ok(candidate);
+        }
+    }
+    which::which(&file_name)
+        .map_err(|_| format!("未找到 {}，请安装 FFmpeg 或设置 SACV_FFMPEG_DIR", file_name))
+}
+
+// 视频元信息（由 ffprobe 解析）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VideoMeta {
+    path: String,
+    duration: Option<f64>,
+    width: Option<u32>,
+    height: Option<u32>,
+    codec: Option<String>,
+    fps: Option<f64>,
+    has_video: bool,
+    has_audio: bool,
+}
+
+fn parse_frame_rate(s: &str) -> Option<f64> {
+    match s.split_once('/') {
+        Some((n, d)) => {
+            let n: f64 = n.parse().ok()?;
+            let d: f64 = d.parse().ok()?;
+            if d == 0.0 {
+                None
+            } else {
+                Some(n / d)
+            }
+        }
+        None => s.parse().ok(),
+    }
+}
+
+fn probe_video_blocking(ffprobe: &std::path::Path, path: &str) -> Result<VideoMeta, String> {
+    if !std::path::Path::new(path).is_file() {
+        return Err(format!("文件不存在: {}", path));
+    }
+    let mut cmd = Command::new(ffprobe);
+    cmd.args([
+        "-v",
+        "error",
+        "-print_format",
+        "json",
+        "-show_format",
+        "-show_streams",
+    ])
+    .arg(path)
+    .stdin(Stdio::null());
+    let output = apply_windows_no_window(cmd)
+        .output()
+        .map_err(|e| format!("执行 ffprobe 失败: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "ffprobe 解析失败: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let v: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("解析 ffprobe 输出失败: {}", e))?;
+    let streams = v
+        .get("streams")
+        .and_then(|s| s.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let video = streams
+        .iter()
+        .find(|s| s.get("codec_type").and_then(|t| t.as_str()) == Some("video"));
+    let has_audio = streams
+        .iter()
+        .any(|s| s.get("codec_type").and_then(|t| t.as_str()) == Some("audio"));
+    let duration = v
+        .get("format")
+        .and_then(|f| f.get("duration"))
+        .and_then(|d| d.as_str())
+        .and_then(|d| d.parse::<f64>().ok());
+    Ok(VideoMeta {
+        path: path.to_string(),
+        duration,
+        width: video
+            .and_then(|s| s.get("width"))
+            .and_then(|w| w.as_u64())
+            .map(|w| w as u32),
+        height: video
+            .and_then(|s| s.get("height"))
+            .and_then(|h| h.as_u64())
+            .map(|h| h as u32),
+        codec: video
+            .and_then(|s| s.get("codec_name"))
+            .and_then(|c| c.as_str())
+            .map(|c| c.to_string()),
+        fps: video
+            .and_then(|s| s.get("avg_frame_rate"))
+            .and_then(|r| r.as_str())
+            .and_then(parse_frame_rate),
+        has_video: video.is_some(),
+        has_audio,
+    })
+}
+
+// Tauri命令：探测视频元信息（时长、分辨率、编码等）
+#[tauri::command]
+async fn probe_video(app_handle: AppHandle, path: String) -> Result<VideoMeta, String> {
+    let ffprobe = resolve_ffmpeg_tool(&app_handle, "ffprobe")?;
+    tauri::async_runtime::spawn_blocking(move || probe_video_blocking(&ffprobe, &path))
+        .await
+        .map_err(|e| format!("探测任务异常: {}", e))?
+}
+
+// 批量探测的单项结果：成功时带 meta，失败时带该文件的错误信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VideoMetaResult {
+    path: String,
+    meta: Option<VideoMeta>,
+    error: Option<String>,
+}
+
+const PROBE_CONCURRENCY: usize = 4;
+
+// Tauri命令：批量并行探测视频元信息（最多同时运行 PROBE_CONCURRENCY 个 ffprobe），返回顺序与输入一致
+#[tauri::command]
+async fn probe_videos(
+    app_handle: AppHandle,
+    paths: Vec<String>,
+) -> Result<Vec<VideoMetaResult>, String> {
+    let ffprobe = Arc::new(resolve_ffmpeg_tool(&app_handle, "ffprobe")?);
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(PROBE_CONCURRENCY));
+    let mut handles = Vec::with_capacity(paths.len());
+    for path in paths {
+        let ffprobe = ffprobe.clone();
+        let semaphore = semaphore.clone();
+        handles.push(tauri::async_runtime::spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let p = path.clone();
+            let res =
+                tauri::async_runtime::spawn_blocking(move || probe_video_blocking(&ffprobe, &p))
+                    .await
+                    .map_err(|e| format!("探测任务异常: {}", e))
+                    .and_then(|r| r);
+            match res {
+                Ok(meta) => VideoMetaResult {
+                    path,
+                    meta: Some(meta),
+                    error: None,
+                },
+                Err(e) => VideoMetaResult {
+                    path,
+                    meta: None,
+                    error: Some(e),
+                },
+            }
+        }));
+    }
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(handle.await.map_err(|e| format!("探测任务异常: {}", e))?);
+    }
+    Ok(results)
+}
+
+// 缩略图输出格式：(格式名, 扩展名, 所需编码器, 编码参数)
+static THUMBNAIL_FORMATS: [(&str, &str, &str, &[&str]); 3] = [
+    ("jpg", "jpg", "mjpeg", &["-q:v", "3"]),
+    ("png", "png", "png", &["-c:v", "png"]),
+    (
+        "webp",
+        "webp",
+        "libwebp",
+        &["-c:v", "libwebp", "-quality", "80"],
+    ),
+];
+
+#[derive(Serialize)]
+struct ThumbnailResult {
+    path: String,
+    // 实际输出格式
+    format: String,
+    // 请求的格式当前 FFmpeg 不支持，已回退为 jpg
+    fallback: bool,
+}
+
+// Tauri命令：抽取视频某一时刻的缩略图，输出到临时文件并返回路径
+// format 为 jpg（默认）/png/webp，FFmpeg 缺少对应编码器时回退 jpg 并标注 fallback；
+// time_sec 超出时长时回退到时长的一半；无视频流时直接报错
+#[tauri::command]
+async fn extract_thumbnail(
+    app_handle: AppHandle,
+    path: String,
+    time_sec: f64,
+    max_width: u32,
+    format: Option<String>,
+) -> Result<ThumbnailResult, String> {
+    let requested = format
+        .map(|f| f.trim().to_lowercase())
+        .filter(|f| !f.is_empty())
+        .map(|f| if f == "jpeg" { "jpg".to_string() } else { f })
+        .unwrap_or_else(|| "jpg".to_string());
+    let spec = THUMBNAIL_FORMATS
+        .iter()
+        .find(|(name, ..)| *name == requested)
+        .ok_or_else(|| format!("不支持的缩略图格式: {}（支持 jpg/png/webp）", requested))?;
+    let ffprobe = resolve_ffmpeg_tool(&app_handle, "ffprobe")?;
+    let ffmpeg = resolve_ffmpeg_tool(&app_handle, "ffmpeg")?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let meta = probe_video_blocking(&ffprobe, &path)?;
+        if !meta.has_video {
+            return Err(format!("文件不包含视频流: {}", path));
+        }
+        let mut t = if time_sec.is_finite() && time_sec > 0.0 {
+            time_sec
+        } else {
+            0.0
+        };
+        if let Some(d) = meta.duration {
+            if t >= d {
+                t = (d / 2.0).max(0.0);
+            }
+        }
+        let width = if max_width == 0 { 320 } else { max_width };
+        let (spec, fallback) = if spec.0 == "jpg" {
+            (spec, false)
+        } else {
+            let listed = list_ffmpeg_encoders(&ffmpeg).unwrap_or_default();
+            if listed.iter().any(|l| l == spec.2) {
+                (spec, false)
+            } else {
+                (&THUMBNAIL_FORMATS[0], true)
             }
-            Err(e) => Err(format!("检查进程状态失败: {}", e)),
+        };
+        let (format_name, ext, _, codec_args) = *spec;
+        let out_dir = std::env::temp_dir().join("super_auto_cut_thumbnails");
+        std::fs::create_dir_all(&out_dir).map_err(|e| format!("创建缩略图目录失败: {}", e))?;
+        let mut token = [0u8; 8];
+        OsRng.fill_bytes(&mut token);
+        let token: String = token.iter().map(|b| format!("{:02x}", b)).collect();
+        let out_path = out_dir.join(format!("thumb_{}.{}", token, ext));
+
+        let mut cmd = Command::new(&ffmpeg);
+        cmd.args(["-hide_banner", "-loglevel", "error", "-y", "-ss"])
+            .arg(format!("{:.3}", t))
+            .arg("-i")
+            .arg(&path)
+            .args(["-frames:v", "1", "-vf"])
+            // 仅在原图更宽时缩小，保持宽高比且高度为偶数
+            .arg(format!("scale='min({},iw)':-2", width))
+            .args(codec_args)
+            .arg(&out_path)
+            .stdin(Stdio::null());
+        let output = apply_windows_no_window(cmd)
+            .output()
+            .map_err(|e| format!("执行 ffmpeg 失败: {}", e))?;
+        if !output.status.success() || !out_path.is_file() {
+            let _ = std::fs::remove_file(&out_path);
+            return Err(format!(
+                "抽取缩略图失败: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
         }
-    } else {
-        Ok(BackendStatus {
-            running: false,
-            port: 0,
-            pid: None,
-            boot_token: None,
+        Ok(ThumbnailResult {
+            path: out_path.to_string_lossy().to_string(),
+            format: format_name.to_string(),
+            fallback,
+        })
+    })
+    .await
+    .map_err(|e| format!("缩略图任务异常: {}", e))?
+}
+
+// 关注的硬件编码器；`-encoders` 列出的仅代表编译进了 ffmpeg，需实际编码一帧确认驱动/硬件可用
+const HW_ENCODER_CANDIDATES: &[&str] = &[
+    "h264_nvenc",
+    "hevc_nvenc",
+    "h264_qsv",
+    "hevc_qsv",
+    "h264_videotoolbox",
+    "hevc_videotoolbox",
+    "h264_amf",
+    "hevc_amf",
+];
+
+// 列出 ffmpeg -encoders 中的编码器名
+fn list_ffmpeg_encoders(ffmpeg: &std::path::Path) -> Result<Vec<String>, String> {
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args(["-hide_banner", "-encoders"]).stdin(Stdio::null());
+    let output = apply_windows_no_window(cmd)
+        .output()
+        .map_err(|e| format!("执行 ffmpeg 失败: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "获取编码器列表失败: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    // 每行形如 " V....D h264_nvenc  NVIDIA NVENC H.264 encoder"，第二列为编码器名
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(|name| name.to_string())
+        .collect())
+}
+
+fn hw_encoder_smoke_test(ffmpeg: &std::path::Path, encoder: &str) -> bool {
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args([
+        "-hide_banner",
+        "-loglevel",
+        "error",
+        "-f",
+        "lavfi",
+        "-i",
+        "color=c=black:s=256x256:r=25:d=0.2",
+        "-frames:v",
+        "1",
+        "-c:v",
+        encoder,
+        "-f",
+        "null",
+        "-",
+    ])
+    .stdin(Stdio::null())
+    .stdout(Stdio::null())
+    .stderr(Stdio::null());
+    apply_windows_no_window(cmd)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+// Tauri命令：探测真正可用的硬件编码器
+#[tauri::command]
+async fn detect_hw_encoders(app_handle: AppHandle) -> Result<Vec<String>, String> {
+    let ffmpeg = resolve_ffmpeg_tool(&app_handle, "ffmpeg")?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let listed = list_ffmpeg_encoders(&ffmpeg)?;
+        let available = HW_ENCODER_CANDIDATES
+            .iter()
+            .filter(|enc| listed.iter().any(|l| l.as_str() == **enc))
+            .filter(|enc| hw_encoder_smoke_test(&ffmpeg, enc))
+            .map(|enc| enc.to_string())
+            .collect();
+        Ok(available)
+    })
+    .await
+    .map_err(|e| format!("探测任务异常: {}", e))?
+}
+
+// GPU 信息：backend 为推理可用的加速后端（cuda/metal），memory_mb 为显存（统一内存架构时为 None）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GpuInfo {
+    name: String,
+    vendor: String,
+    memory_mb: Option<u64>,
+    backend: String,
+}
+
+// 解析 nvidia-smi --query-gpu=name,memory.total --format=csv,noheader,nounits 的输出（每行 "名称, 显存MiB"）
+fn parse_nvidia_smi_output(stdout: &str) -> Vec<GpuInfo> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, memory) = line.rsplit_once(',')?;
+            let name = name.trim();
+            if name.is_empty() {
+                return None;
+            }
+            Some(GpuInfo {
+                name: name.to_string(),
+                vendor: "nvidia".to_string(),
+                memory_mb: memory.trim().parse().ok(),
+                backend: "cuda".to_string(),
+            })
+        })
+        .collect()
+}
+
+fn detect_nvidia_gpus() -> Vec<GpuInfo> {
+    let mut cmd = Command::new("nvidia-smi");
+    cmd.args([
+        "--query-gpu=name,memory.total",
+        "--format=csv,noheader,nounits",
+    ])
+    .stdin(Stdio::null())
+    .stderr(Stdio::null());
+    match apply_windows_no_window(cmd).output() {
+        Ok(out) if out.status.success() => {
+            parse_nvidia_smi_output(&String::from_utf8_lossy(&out.stdout))
+        }
+        // 未安装驱动/nvidia-smi 不在 PATH 时视为没有 NVIDIA 显卡
+        _ => Vec::new(),
+    }
+}
+
+// Apple Silicon 的 GPU 与 CPU 同芯片，直接标记 Metal 可用；名称取芯片型号，读不到时用通用名称
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+fn detect_apple_gpu() -> Option<GpuInfo> {
+    let name = Command::new("sysctl")
+        .args(["-n", "machdep.cpu.brand_string"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "Apple Silicon".to_string());
+    Some(GpuInfo {
+        name,
+        vendor: "apple".to_string(),
+        memory_mb: None,
+        backend: "metal".to_string(),
+    })
+}
+
+#[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
+fn detect_apple_gpu() -> Option<GpuInfo> {
+    None
+}
+
+// Tauri命令：探测可用于推理加速的 GPU（NVIDIA 通过 nvidia-smi，Apple Silicon 标记 Metal），没有可用 GPU 时返回空列表
+#[tauri::command]
+async fn detect_gpu() -> Result<Vec<GpuInfo>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut gpus = detect_nvidia_gpus();
+        gpus.extend(detect_apple_gpu());
+        gpus
+    })
+    .await
+    .map_err(|e| format!("探测任务异常: {}", e))
+}
+
+// 应用磁盘占用分项（字节）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StorageBreakdown {
+    backend_extracted: u64,
+    backend_tmp: u64,
+    logs: u64,
+    ffmpeg: u64,
+    download_cache: u64,
+    total: u64,
+}
+
+// 统计路径占用：文件直接取大小，目录用显式栈迭代遍历，不跟随符号链接，读不到的条目跳过
+fn path_size(path: &std::path::Path) -> u64 {
+    let meta = match std::fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(_) => return 0,
+    };
+    if !meta.is_dir() {
+        return if meta.is_file() { meta.len() } else { 0 };
+    }
+    let mut total = 0u64;
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let ft = match entry.file_type() {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+            if ft.is_dir() {
+                stack.push(entry.path());
+            } else if ft.is_file() {
+                total += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+        }
+    }
+    total
+}
+
+// Tauri命令：统计应用各部分的磁盘占用
+#[tauri::command]
+async fn storage_usage(app_handle: AppHandle) -> Result<StorageBreakdown, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取应用数据目录: {}", e))?;
+    // 与启动流程使用同一资源根目录，日志与日志导出收集同一批文件
+    let resource_root = backend_resource_decision(&app_handle).1.root;
+    let log_files = collect_log_files(&app_handle);
+    let tmp_dir = backend_tmp_dir(&app_handle);
+    tauri::async_runtime::spawn_blocking(move || {
+        let backend_extracted = path_size(&app_data_dir.join("superAutoCutVideoBackend"))
+            + path_size(&app_data_dir.join("superAutoCutVideoBackend.partial"));
+        let backend_tmp = path_size(&tmp_dir);
+        let logs = log_files.iter().map(|(_, p)| path_size(p)).sum();
+        let exe_suffix = std::env::consts::EXE_SUFFIX;
+        let ffmpeg = ["ffmpeg", "ffprobe"]
+            .iter()
+            .flat_map(|n| {
+                [
+                    format!("{}{}", n, exe_suffix),
+                    format!("{}{}.partial", n, exe_suffix),
+                ]
+            })
+            .map(|n| path_size(&resource_root.join(n)))
+            .sum();
+        let download_cache = path_size(&resource_root.join("ffmpeg-download.zip"))
+            + path_size(&resource_root.join("ffmpeg-download.zip.partial"));
+        Ok(StorageBreakdown {
+            backend_extracted,
+            backend_tmp,
+            logs,
+            ffmpeg,
+            download_cache,
+            total: backend_extracted + backend_tmp + logs + ffmpeg + download_cache,
         })
+    })
+    .await
+    .map_err(|e| format!("统计任务异常: {}", e))?
+}
+
+// 去掉 Windows canonicalize 产生的扩展长度前缀（\\?\ 与 \\?\UNC\），后端无需也不一定能处理
+fn strip_verbatim_prefix(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{}", rest)
+    } else if let Some(rest) = path.strip_prefix(r"\\?\") {
+        rest.to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+// 解析快捷方式/符号链接指向的真实路径；解析失败时返回 None，由调用方继续使用原始路径
+fn resolve_real_path(path: &str) -> Option<String> {
+    std::fs::canonicalize(path)
+        .ok()
+        .map(|p| strip_verbatim_prefix(&p.to_string_lossy()))
+}
+
+// 按类型划分的文件扩展名（小写、不含点），选择对话框、播放与拖放分类共用
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "mkv", "avi", "wmv", "flv", "webm", "m4v"];
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "wav", "aac", "m4a", "flac", "ogg", "opus", "wma"];
+const SUBTITLE_EXTENSIONS: &[&str] = &["srt", "ass", "ssa", "vtt", "lrc"];
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "bmp", "gif"];
+const PLAYABLE_EXTENSIONS: &[&str] = VIDEO_EXTENSIONS;
+
+// 单个拖放文件：与 FileSelection 一致，附带解析快捷方式/符号链接后的真实路径
+#[derive(Serialize)]
+struct DroppedFile {
+    path: String,
+    real_path: Option<String>,
+}
+
+// 拖放文件按类型分类后的结果；目录与未知扩展名归入 others，由前端决定是否提示
+#[derive(Default, Serialize)]
+struct DroppedFiles {
+    video: Vec<DroppedFile>,
+    audio: Vec<DroppedFile>,
+    subtitle: Vec<DroppedFile>,
+    image: Vec<DroppedFile>,
+    others: Vec<DroppedFile>,
+}
+
+fn classify_dropped_files(paths: &[PathBuf]) -> DroppedFiles {
+    let mut out = DroppedFiles::default();
+    for path in paths {
+        let display = strip_verbatim_prefix(&path.to_string_lossy());
+        let real_path = resolve_real_path(&display);
+        // 符号链接按其指向的真实文件扩展名分类
+        let target = real_path
+            .as_deref()
+            .map(std::path::Path::new)
+            .unwrap_or(path);
+        let ext = if target.is_file() {
+            target
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+        let bucket = match ext.as_str() {
+            e if VIDEO_EXTENSIONS.contains(&e) => &mut out.video,
+            e if AUDIO_EXTENSIONS.contains(&e) => &mut out.audio,
+            e if SUBTITLE_EXTENSIONS.contains(&e) => &mut out.subtitle,
+            e if IMAGE_EXTENSIONS.contains(&e) => &mut out.image,
+            _ => &mut out.others,
+        };
+        bucket.push(DroppedFile {
+            path: display,
+            real_path,
+        });
     }
+    out
 }
 
 // Tauri命令：选择视频文件
@@ -1203,79 +6182,728 @@ async fn get_backend_status(state: State<'_, AppState>) -> Result<BackendStatus,
 async fn select_video_file(app: AppHandle) -> Result<FileSelection, String> {
     let file_path = tauri_plugin_dialog::DialogExt::dialog(&app)
         .file()
-        .add_filter("视频文件", &["mp4", "avi", "mov", "mkv", "wmv", "flv"])
+        .add_filter("视频文件", VIDEO_EXTENSIONS)
         .set_title("选择视频文件")
         .blocking_pick_file();
 
     match file_path {
-        Some(path) => Ok(FileSelection {
-            path: Some(path.to_string()),
-            cancelled: false,
+        Some(path) => {
+            let path = path.to_string();
+            // 授权所选视频所在目录，便于读写同目录的字幕等附属文件
+            if let Some(dir) = std::path::Path::new(&path).parent() {
+                app.state::<AppState>().authorize_dir(dir);
+            }
+            add_to_recent_documents(&app, std::path::Path::new(&path));
+            Ok(FileSelection {
+                real_path: resolve_real_path(&path),
+                path: Some(path),
+                cancelled: false,
+                writable: None,
+            })
+        }
+        None => Ok(FileSelection {
+            path: None,
+            cancelled: true,
+            writable: None,
+            real_path: None,
         }),
+    }
+}
+
+const MAX_TEXT_FILE_BYTES: u64 = 16 * 1024 * 1024;
+
+// 把路径规范化（写入时文件可能尚不存在，规范化其父目录）并校验位于已授权目录内；
+// 规范化会解析 .. 与符号链接，防止借此逃出授权范围
+fn resolve_authorized_path(
+    state: &AppState,
+    path: &str,
+    for_write: bool,
+) -> Result<PathBuf, String> {
+    let raw = std::path::Path::new(path);
+    if !raw.is_absolute() {
+        return Err(format!("仅支持绝对路径: {}", path));
+    }
+    let resolved = if for_write && !raw.exists() {
+        let parent = raw.parent().ok_or_else(|| format!("路径无效: {}", path))?;
+        let name = raw
+            .file_name()
+            .ok_or_else(|| format!("路径无效: {}", path))?;
+        std::fs::canonicalize(parent)
+            .map_err(|e| format!("目录不存在 {:?}: {}", parent, e))?
+            .join(name)
+    } else {
+        std::fs::canonicalize(raw).map_err(|e| format!("文件不存在 {}: {}", path, e))?
+    };
+    let allowed = state
+        .authorized_dirs
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|dir| resolved.starts_with(dir));
+    if allowed {
+        Ok(resolved)
+    } else {
+        Err(format!("无权访问该路径（不在已选择的目录内）: {}", path))
+    }
+}
+
+// Tauri命令：读取已授权目录内的文本文件（UTF-8，最大 16MB）
+#[tauri::command]
+async fn read_text_file(state: State<'_, AppState>, path: String) -> Result<String, String> {
+    let resolved = resolve_authorized_path(&state, &path, false)?;
+    let meta = std::fs::metadata(&resolved).map_err(|e| format!("读取文件信息失败: {}", e))?;
+    if !meta.is_file() {
+        return Err(format!("路径不是文件: {}", path));
+    }
+    if meta.len() > MAX_TEXT_FILE_BYTES {
+        return Err(format!(
+            "文件过大（{} 字节），超过 {} 字节上限",
+            meta.len(),
+            MAX_TEXT_FILE_BYTES
+        ));
+    }
+    std::fs::read_to_string(&resolved).map_err(|e| format!("读取文件失败 {}: {}", path, e))
+}
+
+// Tauri命令：写入已授权目录内的文本文件（覆盖写入，先写临时文件再重命名）
+#[tauri::command]
+async fn write_text_file(
+    state: State<'_, AppState>,
+    path: String,
+    content: String,
+) -> Result<(), String> {
+    let resolved = resolve_authorized_path(&state, &path, true)?;
+    if resolved.is_dir() {
+        return Err(format!("路径是目录: {}", path));
+    }
+    let tmp_path = PathBuf::from(format!("{}.sacv_tmp", resolved.to_string_lossy()));
+    std::fs::write(&tmp_path, content).map_err(|e| format!("写入文件失败 {}: {}", path, e))?;
+    std::fs::rename(&tmp_path, &resolved).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        format!("写入文件失败 {}: {}", path, e)
+    })
+}
+
+// rename 失败是否因为源与目标不在同一磁盘/文件系统（Windows ERROR_NOT_SAME_DEVICE / Unix EXDEV）
+fn is_cross_device_error(e: &std::io::Error) -> bool {
+    #[cfg(target_os = "windows")]
+    let code = 17;
+    #[cfg(not(target_os = "windows"))]
+    let code = 18;
+    e.raw_os_error() == Some(code)
+}
+
+// 移动文件：同盘直接 rename；跨盘先复制到目标目录下的临时文件、校验复制字节数与源文件一致后再 rename 到位并删除源文件，
+// 任何一步失败都清理临时文件，不会留下半截目标
+fn move_file_blocking(
+    src: &std::path::Path,
+    dst: &std::path::Path,
+    overwrite: bool,
+) -> Result<(), String> {
+    if !src.is_absolute() || !dst.is_absolute() {
+        return Err("仅支持绝对路径".to_string());
+    }
+    let meta = std::fs::metadata(src).map_err(|e| format!("源文件不存在 {:?}: {}", src, e))?;
+    if !meta.is_file() {
+        return Err(format!("源路径不是文件: {:?}", src));
+    }
+    if dst.is_dir() {
+        return Err(format!("目标路径是目录: {:?}", dst));
+    }
+    if dst.exists() && !overwrite {
+        return Err(format!("目标文件已存在: {:?}", dst));
+    }
+    if let Some(parent) = dst.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("创建目标目录失败 {:?}: {}", parent, e))?;
+    }
+    match std::fs::rename(src, dst) {
+        Ok(()) => return Ok(()),
+        Err(e) if is_cross_device_error(&e) => {}
+        Err(e) => return Err(format!("移动文件失败 {:?} -> {:?}: {}", src, dst, e)),
+    }
+    let tmp_path = PathBuf::from(format!("{}.sacv_tmp", dst.to_string_lossy()));
+    let copy_result = (|| {
+        let copied =
+            std::fs::copy(src, &tmp_path).map_err(|e| format!("复制文件失败 {:?}: {}", dst, e))?;
+        if copied != meta.len() {
+            return Err(format!("复制后校验不一致 {:?}", dst));
+        }
+        std::fs::rename(&tmp_path, dst).map_err(|e| format!("移动文件失败 {:?}: {}", dst, e))
+    })();
+    if let Err(e) = copy_result {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    // 目标已完整就位，源文件删除失败只记录日志
+    if let Err(e) = std::fs::remove_file(src) {
+        app_log!(warn, "[fs] 移动后删除源文件失败 {:?}: {}", src, e);
+    }
+    Ok(())
+}
+
+// Tauri命令：移动/重命名文件（支持跨盘），overwrite 控制是否覆盖已有目标；源与目标都须位于已授权目录内
+#[tauri::command]
+async fn move_file(
+    state: State<'_, AppState>,
+    src: String,
+    dst: String,
+    overwrite: bool,
+) -> Result<(), String> {
+    let src = resolve_authorized_path(&state, &src, false)?;
+    let dst = resolve_authorized_path(&state, &dst, true)?;
+    tauri::async_runtime::spawn_blocking(move || move_file_blocking(&src, &dst, overwrite))
+        .await
+        .map_err(|e| format!("移动文件任务失败: {}", e))?
+}
+
+// 校验目录可写：不存在则创建，再写入并删除一个临时探测文件
+fn ensure_dir_writable(dir: &std::path::Path) -> Result<(), String> {
+    if !dir.exists() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("创建目录失败 {:?}: {}", dir, e))?;
+    }
+    if !dir.is_dir() {
+        return Err(format!("路径不是目录: {:?}", dir));
+    }
+    let probe = dir.join(format!(".sacv_write_test_{}", std::process::id()));
+    std::fs::write(&probe, b"ok").map_err(|e| format!("目录不可写 {:?}: {}", dir, e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+// Tauri命令：推导默认输出目录
+// 优先级：配置中保存的 output_dir → 系统视频目录 → 系统下载目录 → 应用数据目录下的 output（自动创建）；返回的目录保证存在且可写
+#[tauri::command]
+async fn default_output_dir(app_handle: AppHandle) -> Result<String, String> {
+    let config = load_app_config(&app_handle);
+    if let Some(dir) = config
+        .get("output_dir")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+    {
+        match ensure_dir_writable(std::path::Path::new(dir)) {
+            Ok(()) => return Ok(dir.to_string()),
+            Err(e) => app_log!(error, "配置的输出目录不可用: {}", e),
+        }
+    }
+    let system_dirs = [
+        app_handle.path().video_dir(),
+        app_handle.path().download_dir(),
+    ];
+    for dir in system_dirs.into_iter().flatten() {
+        if dir.is_dir() && ensure_dir_writable(&dir).is_ok() {
+            return Ok(dir.to_string_lossy().to_string());
+        }
+    }
+    let fallback = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取应用数据目录: {}", e))?
+        .join("output");
+    ensure_dir_writable(&fallback)?;
+    Ok(fallback.to_string_lossy().to_string())
+}
+
+// Tauri命令：选择输出目录
+#[tauri::command]
+async fn select_output_directory(app: AppHandle) -> Result<FileSelection, String> {
+    let dir_path = tauri_plugin_dialog::DialogExt::dialog(&app)
+        .file()
+        .set_title("选择输出目录")
+        .blocking_pick_folder();
+
+    match dir_path {
+        Some(path) => {
+            let path = path.to_string();
+            let writable = match ensure_dir_writable(std::path::Path::new(&path)) {
+                Ok(()) => true,
+                Err(e) => {
+                    app_log!(error, "输出目录校验失败: {}", e);
+                    false
+                }
+            };
+            app.state::<AppState>()
+                .authorize_dir(std::path::Path::new(&path));
+            Ok(FileSelection {
+                real_path: resolve_real_path(&path),
+                path: Some(path),
+                cancelled: false,
+                writable: Some(writable),
+            })
+        }
         None => Ok(FileSelection {
             path: None,
             cancelled: true,
+            writable: None,
+            real_path: None,
         }),
     }
 }
 
-// Tauri命令：选择输出目录
+// get_app_info 中不随运行状态变化的字段缓存
+static STATIC_APP_INFO: std::sync::OnceLock<HashMap<String, String>> = std::sync::OnceLock::new();
+
+// Tauri命令：获取应用信息
+#[tauri::command]
+async fn get_app_info(app_handle: AppHandle) -> Result<HashMap<String, String>, String> {
+    // 不变部分（名称/版本/平台/构建信息）只构建一次，后续扩展的较重探测也放在这里
+    let mut info = STATIC_APP_INFO
+        .get_or_init(|| {
+            let mut info = HashMap::new();
+            let pkg = app_handle.package_info();
+            let version = pkg.version.to_string();
+            info.insert("name".to_string(), "AI智能视频剪辑".to_string());
+            info.insert("version".to_string(), version);
+            info.insert(
+                "description".to_string(),
+                "基于AI技术的智能视频剪辑工具".to_string(),
+            );
+            info.insert("os".to_string(), std::env::consts::OS.to_string());
+            info.insert("arch".to_string(), std::env::consts::ARCH.to_string());
+            // 构建信息由 build.rs 注入
+            info.insert(
+                "git_hash".to_string(),
+                option_env!("GIT_HASH").unwrap_or("unknown").to_string(),
+            );
+            info.insert(
+                "build_time".to_string(),
+                option_env!("BUILD_TIME").unwrap_or("unknown").to_string(),
+            );
+            info.insert(
+                "build_profile".to_string(),
+                option_env!("BUILD_PROFILE")
+                    .unwrap_or("unknown")
+                    .to_string(),
+            );
+            info
+        })
+        .clone();
+    // 会变化的部分实时计算（默认后端实例）
+    let backend = app_handle.state::<AppState>().backend(None);
+    info.insert(
+        "backend_port".to_string(),
+        backend.backend_port.lock().unwrap().to_string(),
+    );
+    info.insert(
+        "backend_mode".to_string(),
+        backend.backend_source.lock().unwrap().clone(),
+    );
+    Ok(info)
+}
+
+// Tauri命令：显示通知
+// category 为 "complete" 时默认带提示音，其余（如 "info"）默认静默；受配置中的全局通知/声音开关约束
+#[tauri::command]
+async fn show_notification(
+    app_handle: AppHandle,
+    title: String,
+    body: String,
+    sound: Option<bool>,
+    category: Option<String>,
+) -> Result<(), String> {
+    let config = load_app_config(&app_handle);
+    let flag = |k: &str| config.get(k).and_then(|v| v.as_bool()).unwrap_or(true);
+    if !flag("notifications_enabled") {
+        return Ok(());
+    }
+    let want_sound =
+        sound.unwrap_or(category.as_deref() == Some("complete")) && flag("notification_sound");
+    let mut builder = tauri_plugin_notification::NotificationExt::notification(&app_handle)
+        .builder()
+        .title(&title)
+        .body(&body);
+    if want_sound {
+        builder = builder.sound("default");
+    }
+    builder.show().map_err(|e| format!("显示通知失败: {}", e))?;
+    Ok(())
+}
+
+// Tauri命令：设置全局通知开关与通知声音开关
+#[tauri::command]
+async fn set_notification_prefs(
+    app_handle: AppHandle,
+    enabled: Option<bool>,
+    sound: Option<bool>,
+) -> Result<(), String> {
+    update_app_config(&app_handle, |config| {
+        if let Some(v) = enabled {
+            config.insert("notifications_enabled".to_string(), serde_json::json!(v));
+        }
+        if let Some(v) = sound {
+            config.insert("notification_sound".to_string(), serde_json::json!(v));
+        }
+    })
+}
+
+const MAX_EXTERNAL_URL_LEN: usize = 2048;
+const EXTERNAL_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+// 校验外链：限制长度、拒绝空白与控制字符（部分系统 opener 会把换行后的内容当作参数/命令），
+// 须能解析为 URL 且 scheme 在白名单内，http(s) 必须带主机名
+fn validate_external_url(raw: &str) -> Result<tauri::Url, String> {
+    if raw.is_empty() || raw.len() > MAX_EXTERNAL_URL_LEN {
+        return Err(format!("链接长度不合法（1-{} 字符）", MAX_EXTERNAL_URL_LEN));
+    }
+    if raw.chars().any(|c| c.is_control() || c.is_whitespace()) {
+        return Err("链接包含空白或控制字符".to_string());
+    }
+    let url = tauri::Url::parse(raw).map_err(|e| format!("链接格式不合法: {}", e))?;
+    if !EXTERNAL_URL_SCHEMES.contains(&url.scheme()) {
+        return Err(format!("不支持打开该类型的链接: {}", url.scheme()));
+    }
+    if url.scheme() != "mailto" && url.host_str().map_or(true, |h| h.is_empty()) {
+        return Err("链接缺少主机名".to_string());
+    }
+    Ok(url)
+}
+
+// Tauri命令：打开外部链接
+#[tauri::command]
+async fn open_external_link(app: AppHandle, url: String) -> Result<(), String> {
+    let url = validate_external_url(url.trim())?;
+    tauri_plugin_opener::OpenerExt::opener(&app)
+        .open_url(url.as_str(), None::<String>)
+        .map_err(|e| format!("打开链接失败: {}", e))
+}
+
+// 用系统文件管理器打开目录
+fn open_in_file_manager(app: &AppHandle, dir: &std::path::Path) -> Result<(), String> {
+    if !dir.is_dir() {
+        return Err(format!("目录不存在: {}", dir.to_string_lossy()));
+    }
+    tauri_plugin_opener::OpenerExt::opener(app)
+        .open_path(dir.to_string_lossy().to_string(), None::<String>)
+        .map_err(|e| format!("打开目录失败: {}", e))
+}
+
+// Tauri命令：用系统默认播放器打开视频文件（预览成片）
+#[tauri::command]
+async fn play_file(app: AppHandle, path: String) -> Result<(), String> {
+    let file = std::path::Path::new(&path);
+    if !file.is_file() {
+        return Err(format!("文件不存在: {}", path));
+    }
+    let ext = file
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if !PLAYABLE_EXTENSIONS.contains(&ext.as_str()) {
+        return Err(format!("不支持播放的文件类型: {}", path));
+    }
+    tauri_plugin_opener::OpenerExt::opener(&app)
+        .open_path(path.clone(), None::<String>)
+        .map_err(|e| format!("打开文件失败: {}", e))
+}
+
+// Tauri命令：在系统文件管理器中定位并选中文件
+#[tauri::command]
+async fn reveal_in_file_manager(app: AppHandle, path: String) -> Result<(), String> {
+    if !std::path::Path::new(&path).exists() {
+        return Err(format!("文件不存在: {}", path));
+    }
+    tauri_plugin_opener::OpenerExt::opener(&app)
+        .reveal_item_in_dir(&path)
+        .map_err(|e| format!("定位文件失败: {}", e))
+}
+
+// Tauri命令：获取后端日志文件路径
+#[tauri::command]
+async fn get_backend_log_path() -> Result<String, String> {
+    Ok(backend_log_path().to_string_lossy().to_string())
+}
+
+// Tauri命令：打开日志所在文件夹
+#[tauri::command]
+async fn open_log_folder(app: AppHandle) -> Result<(), String> {
+    let log_path = backend_log_path();
+    let dir = log_path
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(std::env::temp_dir);
+    open_in_file_manager(&app, &dir)
+}
+
+// Tauri命令：获取配置文件 config.json 的绝对路径（文件可能尚未创建）
+#[tauri::command]
+async fn get_config_path(app: AppHandle) -> Result<String, String> {
+    Ok(app_config_path(&app)?.to_string_lossy().to_string())
+}
+
+// Tauri命令：打开配置文件所在文件夹（不存在时先创建）
+#[tauri::command]
+async fn open_config_folder(app: AppHandle) -> Result<(), String> {
+    let config_path = app_config_path(&app)?;
+    let dir = config_path
+        .parent()
+        .map(|p| p.to_path_buf())
+        .ok_or_else(|| "无法获取配置目录".to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("创建配置目录失败: {}", e))?;
+    open_in_file_manager(&app, &dir)
+}
+
+// Tauri命令：获取应用使用的各目录绝对路径（目录可能尚未创建），供前端展示或引导清理
+#[tauri::command]
+async fn get_app_dirs(app_handle: AppHandle) -> Result<HashMap<String, String>, String> {
+    let path = app_handle.path();
+    let dirs = [
+        ("app_data_dir", path.app_data_dir()),
+        ("app_cache_dir", path.app_cache_dir()),
+        ("app_config_dir", path.app_config_dir()),
+        ("app_log_dir", path.app_log_dir()),
+    ];
+    let mut out = HashMap::new();
+    for (key, dir) in dirs {
+        let dir = dir.map_err(|e| format!("无法获取 {}: {}", key, e))?;
+        out.insert(key.to_string(), dir.to_string_lossy().to_string());
+    }
+    out.insert(
+        "backend_tmp_dir".to_string(),
+        backend_tmp_dir(&app_handle).to_string_lossy().to_string(),
+    );
+    out.insert(
+        "temp_dir".to_string(),
+        std::env::temp_dir().to_string_lossy().to_string(),
+    );
+    Ok(out)
+}
+
+// 日志相关文件（包内名称, 路径）：后端日志及轮转、应用日志及轮转、崩溃快照；日志导出与磁盘占用统计共用
+fn collect_log_files(app_handle: &AppHandle) -> Vec<(String, PathBuf)> {
+    let log_path = backend_log_path();
+    let log_name = log_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "super_auto_cut_backend.log".to_string());
+    let mut log_files = vec![(log_name.clone(), log_path.clone())];
+    for i in 1..=2 {
+        log_files.push((
+            format!("{}.{}", log_name, i),
+            PathBuf::from(format!("{}.{}", log_path.to_string_lossy(), i)),
+        ));
+    }
+    let app_log = app_log_path();
+    log_files.push(("super_auto_cut_app.log".to_string(), app_log.clone()));
+    log_files.push((
+        "super_auto_cut_app.log.1".to_string(),
+        PathBuf::from(format!("{}.1", app_log.to_string_lossy())),
+    ));
+    for snapshot in crash_snapshot_files(app_handle) {
+        if let Some(name) = snapshot.file_name() {
+            log_files.push((
+                format!("crash/{}", name.to_string_lossy()),
+                snapshot.clone(),
+            ));
+        }
+    }
+    log_files
+}
+
+// Tauri命令：把日志、配置与诊断信息打包为带时间戳的 zip（boot_token 脱敏），返回 zip 路径
 #[tauri::command]
-async fn select_output_directory(app: AppHandle) -> Result<FileSelection, String> {
-    let dir_path = tauri_plugin_dialog::DialogExt::dialog(&app)
-        .file()
-        .set_title("选择输出目录")
-        .blocking_pick_folder();
+async fn export_logs_bundle(app_handle: AppHandle) -> Result<String, String> {
+    let mut entries: Vec<(String, String)> = Vec::new();
 
-    match dir_path {
-        Some(path) => Ok(FileSelection {
-            path: Some(path.to_string()),
-            cancelled: false,
-        }),
-        None => Ok(FileSelection {
-            path: None,
-            cancelled: true,
-        }),
+    let mut log_files = collect_log_files(&app_handle);
+    if let Ok(config_path) = app_config_path(&app_handle) {
+        log_files.push(("config.json".to_string(), config_path));
+    }
+    if let Ok(data_dir) = app_handle.path().app_data_dir() {
+        log_files.push((
+            "backend.pid.json".to_string(),
+            data_dir.join("backend.pid.json"),
+        ));
+    }
+    for (name, path) in log_files {
+        if let Ok(bytes) = std::fs::read(&path) {
+            entries.push((name, String::from_utf8_lossy(&bytes).to_string()));
+        }
+    }
+
+    let app_info = get_app_info(app_handle.clone()).await?;
+    entries.push((
+        "app_info.json".to_string(),
+        serde_json::to_string_pretty(&app_info).unwrap_or_default(),
+    ));
+    match preflight_check(app_handle.clone()).await {
+        Ok(report) => entries.push((
+            "preflight.json".to_string(),
+            serde_json::to_string_pretty(&report).unwrap_or_default(),
+        )),
+        Err(e) => entries.push(("preflight_error.txt".to_string(), e)),
+    }
+    let trace = app_handle
+        .state::<AppState>()
+        .backend(None)
+        .backend_boot_trace
+        .lock()
+        .unwrap()
+        .clone();
+    entries.push((
+        "boot_trace.json".to_string(),
+        serde_json::to_string_pretty(&trace).unwrap_or_default(),
+    ));
+
+    let out_dir = app_handle
+        .path()
+        .download_dir()
+        .ok()
+        .filter(|d| d.is_dir())
+        .unwrap_or_else(std::env::temp_dir);
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let zip_path = out_dir.join(format!("sacv-logs-{}.zip", stamp));
+    let file = std::fs::File::create(&zip_path)
+        .map_err(|e| format!("创建文件失败 {:?}: {}", zip_path, e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in entries {
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| format!("写入压缩包失败 {}: {}", name, e))?;
+        std::io::Write::write_all(&mut zip, redact_secrets(&content).as_bytes())
+            .map_err(|e| format!("写入压缩包失败 {}: {}", name, e))?;
     }
+    zip.finish().map_err(|e| format!("写入压缩包失败: {}", e))?;
+    Ok(zip_path.to_string_lossy().to_string())
 }
 
-// Tauri命令：获取应用信息
-#[tauri::command]
-async fn get_app_info(app_handle: AppHandle) -> Result<HashMap<String, String>, String> {
-    let mut info = HashMap::new();
-    let pkg = app_handle.package_info();
-    let version = pkg.version.to_string();
-    info.insert("name".to_string(), "AI智能视频剪辑".to_string());
-    info.insert("version".to_string(), version);
-    info.insert(
-        "description".to_string(),
-        "基于AI技术的智能视频剪辑工具".to_string(),
-    );
-    Ok(info)
+// 运行外部命令并把 input 写入其标准输入，返回是否成功退出
+fn run_with_stdin(cmd: Command, input: &str) -> bool {
+    use std::io::Write;
+    let mut cmd = apply_windows_no_window(cmd);
+    cmd.stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let mut child = match cmd.spawn() {
+        Ok(c) => c,
+        Err(_) => return false,
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        if stdin.write_all(input.as_bytes()).is_err() {
+            let _ = child.kill();
+            return false;
+        }
+    }
+    child.wait().map(|s| s.success()).unwrap_or(false)
 }
 
-// Tauri命令：显示通知
-#[tauri::command]
-async fn show_notification(
-    app_handle: AppHandle,
-    title: String,
-    body: String,
-) -> Result<(), String> {
-    tauri_plugin_notification::NotificationExt::notification(&app_handle)
-        .builder()
-        .title(&title)
-        .body(&body)
-        .show()
-        .map_err(|e| format!("显示通知失败: {}", e))?;
-    Ok(())
+// 把文件对象放入系统剪贴板（Windows: CF_HDROP，macOS: file URL，Linux: text/uri-list）
+fn copy_file_object_to_clipboard(path: &std::path::Path) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        let path_q = path.to_string_lossy().replace('\'', "''");
+        let mut cmd = Command::new("powershell");
+        cmd.args([
+            "-NoLogo",
+            "-NoProfile",
+            "-NonInteractive",
+            "-STA",
+            "-Command",
+            &format!("Set-Clipboard -LiteralPath '{}'", path_q),
+        ]);
+        return apply_windows_no_window(cmd)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let path_q = path
+            .to_string_lossy()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+        return Command::new("osascript")
+            .arg("-e")
+            .arg(format!("set the clipboard to POSIX file \"{}\"", path_q))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let uri = match reqwest::Url::from_file_path(path) {
+            Ok(u) => u.to_string(),
+            Err(_) => return false,
+        };
+        let payload = format!("{}\r\n", uri);
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() && which::which("wl-copy").is_ok() {
+            let mut cmd = Command::new("wl-copy");
+            cmd.args(["--type", "text/uri-list"]);
+            if run_with_stdin(cmd, &payload) {
+                return true;
+            }
+        }
+        if which::which("xclip").is_ok() {
+            let mut cmd = Command::new("xclip");
+            cmd.args(["-selection", "clipboard", "-t", "text/uri-list"]);
+            return run_with_stdin(cmd, &payload);
+        }
+        false
+    }
 }
 
-// Tauri命令：打开外部链接
+// 回退：把路径文本放入剪贴板
+fn copy_text_to_clipboard(text: &str) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new("powershell");
+        cmd.args([
+            "-NoLogo",
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Set-Clipboard -Value ([Console]::In.ReadToEnd().TrimEnd())",
+        ]);
+        return run_with_stdin(cmd, text);
+    }
+    #[cfg(target_os = "macos")]
+    {
+        return run_with_stdin(Command::new("pbcopy"), text);
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() && which::which("wl-copy").is_ok() {
+            if run_with_stdin(Command::new("wl-copy"), text) {
+                return true;
+            }
+        }
+        if which::which("xclip").is_ok() {
+            let mut cmd = Command::new("xclip");
+            cmd.args(["-selection", "clipboard"]);
+            if run_with_stdin(cmd, text) {
+                return true;
+            }
+        }
+        if which::which("xsel").is_ok() {
+            let mut cmd = Command::new("xsel");
+            cmd.args(["--clipboard", "--input"]);
+            return run_with_stdin(cmd, text);
+        }
+        false
+    }
+}
+
+// Tauri命令：把文件复制到系统剪贴板（可直接在资源管理器/访达中粘贴）
 #[tauri::command]
-async fn open_external_link(app: AppHandle, url: String) -> Result<(), String> {
-    tauri_plugin_opener::OpenerExt::opener(&app)
-        .open_url(url, None::<String>)
-        .map_err(|e| format!("打开链接失败: {}", e))
+async fn copy_file_to_clipboard(_app: AppHandle, path: String) -> Result<(), String> {
+    let file_path = PathBuf::from(&path);
+    if !file_path.exists() {
+        return Err(format!("文件不存在: {}", path));
+    }
+    if copy_file_object_to_clipboard(&file_path) {
+        return Ok(());
+    }
+    if copy_text_to_clipboard(&path) {
+        return Ok(());
+    }
+    Err("复制到剪贴板失败：当前系统不支持文件或文本剪贴板操作".to_string())
 }
 
 #[tauri::command]
@@ -1283,7 +6911,9 @@ async fn minimize_main_window(app: AppHandle) -> Result<(), String> {
     let window = app
         .get_webview_window("main")
         .ok_or_else(|| "主窗口不存在".to_string())?;
-    window.minimize().map_err(|e| format!("最小化窗口失败: {}", e))
+    window
+        .minimize()
+        .map_err(|e| format!("最小化窗口失败: {}", e))
 }
 
 #[tauri::command]
@@ -1333,6 +6963,13 @@ async fn close_main_window(app: AppHandle) -> Result<(), String> {
         .get_webview_window("main")
         .ok_or_else(|| "主窗口不存在".to_string())?;
 
+    if !close_to_tray_enabled(&app) {
+        app.state::<AppState>()
+            .app_is_quitting
+            .store(true, Ordering::SeqCst);
+        return window.close().map_err(|e| format!("关闭窗口失败: {}", e));
+    }
+
     window.hide().map_err(|e| format!("隐藏窗口失败: {}", e))?;
 
     let _ = tauri_plugin_notification::NotificationExt::notification(&app)
@@ -1344,8 +6981,531 @@ async fn close_main_window(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+// Tauri命令：隐藏主窗口到托盘（后端保持运行）
+#[tauri::command]
+async fn hide_to_tray(app: AppHandle) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| "主窗口不存在".to_string())?;
+    window.hide().map_err(|e| format!("隐藏窗口失败: {}", e))
+}
+
+// Tauri命令：从托盘恢复显示主窗口并聚焦
+#[tauri::command]
+async fn show_from_tray(app: AppHandle) -> Result<(), String> {
+    if app.get_webview_window("main").is_none() {
+        return Err("主窗口不存在".to_string());
+    }
+    focus_main_window(&app);
+    Ok(())
+}
+
+// 关闭主窗口时是否最小化到托盘（配置 close_to_tray，默认开启；关闭时点关闭按钮即退出应用）
+fn close_to_tray_enabled(app: &AppHandle) -> bool {
+    load_app_config(app)
+        .get("close_to_tray")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+// Tauri命令：设置关闭窗口时是否最小化到托盘
+#[tauri::command]
+async fn set_close_to_tray(app_handle: AppHandle, enabled: bool) -> Result<(), String> {
+    update_app_config(&app_handle, |config| {
+        config.insert("close_to_tray".to_string(), serde_json::json!(enabled));
+    })
+}
+
+// 应用配置文件（app_config_dir/config.json），以 JSON 对象读写
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+fn app_config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("无法获取应用配置目录: {}", e))?;
+    Ok(dir.join("config.json"))
+}
+
+fn load_app_config(app: &AppHandle) -> serde_json::Map<String, serde_json::Value> {
+    app_config_path(app)
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_default()
+}
+
+// 读取-修改-写回配置；先写临时文件再重命名，避免写坏配置
+fn update_app_config(
+    app: &AppHandle,
+    f: impl FnOnce(&mut serde_json::Map<String, serde_json::Value>),
+) -> Result<(), String> {
+    let _guard = CONFIG_LOCK.lock().unwrap();
+    let path = app_config_path(app)?;
+    let mut config = load_app_config(app);
+    f(&mut config);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&serde_json::Value::Object(config))
+        .map_err(|e| format!("序列化配置失败: {}", e))?;
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, content).map_err(|e| format!("写入配置失败: {}", e))?;
+    std::fs::rename(&tmp_path, &path).map_err(|e| format!("写入配置失败: {}", e))
+}
+
+// 配置导出格式版本；导入时拒绝更高版本导出的文件
+const CONFIG_EXPORT_VERSION: u64 = 1;
+// 导出/导入时排除的本机相关字段（窗口几何）
+const CONFIG_LOCAL_ONLY_KEYS: [&str; 1] = ["window"];
+
+// 校验单个可导入配置字段的类型与取值；未知字段拒绝，避免写入当前版本无法解析的内容
+fn validate_config_field(key: &str, value: &serde_json::Value) -> Result<(), String> {
+    let ok = match key {
+        "output_dir" | "http_proxy" => value.is_string(),
+        "language" => value.as_str().map_or(false, is_valid_language_tag),
+        "notifications_enabled"
+        | "notification_sound"
+        | "close_to_tray"
+        | "auto_reconnect"
+        | "add_to_recent" => value.is_boolean(),
+        "idle_timeout_secs" | "start_timeout_secs" => value.is_u64(),
+        "idle_action" => matches!(value.as_str(), Some("sleep") | Some("stop")),
+        "backend_source" => value
+            .as_str()
+            .map_or(false, |m| BACKEND_SOURCE_MODES.contains(&m)),
+        "backend_log_level" => value
+            .as_str()
+            .map_or(false, |l| BACKEND_LOG_LEVELS.contains(&l)),
+        "backend_args" | "extra_path" => value
+            .as_array()
+            .map_or(false, |arr| arr.iter().all(|a| a.is_string())),
+        "backend_profiles" => value.as_object().map_or(false, |profiles| {
+            profiles.iter().all(|(name, p)| {
+                validate_profile(&Some(name.clone())).is_ok()
+                    && p.get("env").map_or(true, |env| env.is_object())
+            })
+        }),
+        _ => return Err(format!("未知配置字段: {}", key)),
+    };
+    if ok {
+        Ok(())
+    } else {
+        Err(format!("配置字段 {} 的值不合法: {}", key, value))
+    }
+}
+
+// Tauri命令：导出应用配置到 path（为空时弹出保存对话框），返回导出文件路径；用户取消时返回 None
+#[tauri::command]
+async fn export_config(
+    app_handle: AppHandle,
+    path: Option<String>,
+) -> Result<Option<String>, String> {
+    let path = match path.filter(|p| !p.trim().is_empty()) {
+        Some(p) => p,
+        None => match tauri_plugin_dialog::DialogExt::dialog(&app_handle)
+            .file()
+            .set_title("导出配置")
+            .add_filter("JSON", &["json"])
+            .set_file_name("sacv-config.json")
+            .blocking_save_file()
+        {
+            Some(p) => p.to_string(),
+            None => return Ok(None),
+        },
+    };
+    let mut config = load_app_config(&app_handle);
+    for key in CONFIG_LOCAL_ONLY_KEYS {
+        config.remove(key);
+    }
+    let doc = serde_json::json!({
+        "format": "sacv-config",
+        "config_version": CONFIG_EXPORT_VERSION,
+        "app_version": app_handle.package_info().version.to_string(),
+        "config": config,
+    });
+    let content =
+        serde_json::to_string_pretty(&doc).map_err(|e| format!("序列化配置失败: {}", e))?;
+    std::fs::write(&path, content).map_err(|e| format!("写入配置文件失败 {}: {}", path, e))?;
+    Ok(Some(path))
+}
+
+// Tauri命令：从 path（为空时弹出选择对话框）导入配置，校验版本与字段后合并到当前配置，返回导入的字段名；
+// 任一字段不合法则整体拒绝，不改动现有配置
+#[tauri::command]
+async fn import_config(app_handle: AppHandle, path: Option<String>) -> Result<Vec<String>, String> {
+    let path = match path.filter(|p| !p.trim().is_empty()) {
+        Some(p) => p,
+        None => match tauri_plugin_dialog::DialogExt::dialog(&app_handle)
+            .file()
+            .set_title("导入配置")
+            .add_filter("JSON", &["json"])
+            .blocking_pick_file()
+        {
+            Some(p) => p.to_string(),
+            None => return Ok(Vec::new()),
+        },
+    };
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("读取配置文件失败 {}: {}", path, e))?;
+    let doc: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("配置文件不是合法的 JSON: {}", e))?;
+    if doc.get("format").and_then(|v| v.as_str()) != Some("sacv-config") {
+        return Err("不是本应用导出的配置文件".to_string());
+    }
+    let version = doc
+        .get("config_version")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| "配置文件缺少版本号 config_version".to_string())?;
+    if version > CONFIG_EXPORT_VERSION {
+        return Err(format!(
+            "配置文件版本 {} 高于当前支持的版本 {}，请升级应用后再导入",
+            version, CONFIG_EXPORT_VERSION
+        ));
+    }
+    let imported = doc
+        .get("config")
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| "配置文件缺少 config 对象".to_string())?
+        .clone();
+    let mut keys = Vec::new();
+    for (key, value) in &imported {
+        if CONFIG_LOCAL_ONLY_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        validate_config_field(key, value)?;
+        keys.push(key.clone());
+    }
+    update_app_config(&app_handle, |config| {
+        for key in &keys {
+            config.insert(key.clone(), imported[key].clone());
+        }
+    })?;
+    Ok(keys)
+}
+
+// 保存主窗口几何信息（逻辑像素）；最大化时只记录状态，保留还原尺寸
+fn save_window_state(window: &tauri::Window) {
+    if window.is_minimized().unwrap_or(false) {
+        return;
+    }
+    let app = window.app_handle();
+    let maximized = window.is_maximized().unwrap_or(false);
+    let mut entry = load_app_config(app)
+        .get("window")
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_default();
+    entry.insert("maximized".to_string(), serde_json::json!(maximized));
+    if !maximized {
+        let scale = window.scale_factor().unwrap_or(1.0);
+        if let (Ok(size), Ok(pos)) = (window.inner_size(), window.outer_position()) {
+            let size = size.to_logical::<f64>(scale);
+            let pos = pos.to_logical::<f64>(scale);
+            entry.insert("width".to_string(), serde_json::json!(size.width));
+            entry.insert("height".to_string(), serde_json::json!(size.height));
+            entry.insert("x".to_string(), serde_json::json!(pos.x));
+            entry.insert("y".to_string(), serde_json::json!(pos.y));
+        }
+    }
+    let _ = update_app_config(app, |config| {
+        config.insert("window".to_string(), serde_json::Value::Object(entry));
+    });
+}
+
+// resize/move 事件频繁，去抖 500ms 后再落盘
+fn schedule_save_window_state(window: &tauri::Window) {
+    if window.label() != "main" {
+        return;
+    }
+    let state = window.app_handle().state::<AppState>();
+    let gen_ref = state.window_state_gen.clone();
+    let gen = gen_ref.fetch_add(1, Ordering::SeqCst) + 1;
+    let window = window.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        if gen_ref.load(Ordering::SeqCst) == gen {
+            save_window_state(&window);
+        }
+    });
+}
+
+// 判断窗口左上角标题栏区域是否落在某个显示器内（多屏拔掉后不恢复到屏幕外）
+fn is_position_visible(app: &AppHandle, x: f64, y: f64) -> bool {
+    let monitors = match app.available_monitors() {
+        Ok(m) => m,
+        Err(_) => return false,
+    };
+    let (px, py) = (x + 50.0, y + 20.0);
+    monitors.iter().any(|m| {
+        let scale = m.scale_factor();
+        let pos = m.position().to_logical::<f64>(scale);
+        let size = m.size().to_logical::<f64>(scale);
+        px >= pos.x && px < pos.x + size.width && py >= pos.y && py < pos.y + size.height
+    })
+}
+
+// 按保存的几何信息恢复主窗口
+fn restore_window_state(app: &AppHandle) {
+    let config = load_app_config(app);
+    let entry = match config.get("window").and_then(|v| v.as_object()) {
+        Some(e) => e,
+        None => return,
+    };
+    let window = match app.get_webview_window("main") {
+        Some(w) => w,
+        None => return,
+    };
+    let num = |k: &str| entry.get(k).and_then(|v| v.as_f64());
+    if let (Some(w), Some(h)) = (num("width"), num("height")) {
+        if w >= 400.0 && h >= 300.0 {
+            let _ = window.set_size(tauri::LogicalSize::new(w, h));
+        }
+    }
+    if let (Some(x), Some(y)) = (num("x"), num("y")) {
+        if is_position_visible(app, x, y) {
+            let _ = window.set_position(tauri::LogicalPosition::new(x, y));
+        }
+    }
+    if entry
+        .get("maximized")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+    {
+        let _ = window.maximize();
+    }
+}
+
+// 把主窗口从隐藏/最小化状态带到最前（二次启动、托盘点击共用）
+fn focus_main_window(app: &AppHandle) {
+    // macOS：隐藏到托盘后需先激活应用本身，dock 图标对应的应用才会回到前台
+    #[cfg(target_os = "macos")]
+    {
+        let _ = app.show();
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+        // Linux：部分窗口管理器会拒绝 set_focus，退而请求用户注意（任务栏闪烁）
+        #[cfg(target_os = "linux")]
+        {
+            if !window.is_focused().unwrap_or(false) {
+                let _ =
+                    window.request_user_attention(Some(tauri::UserAttentionType::Informational));
+            }
+        }
+    }
+}
+
+// 命令行批量输入的文件清单；missing 为不存在或不是文件的条目（原样返回便于前端提示）
+#[derive(Debug, Clone, Default, Serialize)]
+struct BatchFiles {
+    files: Vec<String>,
+    missing: Vec<String>,
+}
+
+// 解析 --batch <listfile> / --batch=<listfile>；listfile 为 - 时从标准输入读取
+fn batch_list_source(args: &[String]) -> Option<String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--batch" {
+            return iter.next().cloned();
+        }
+        if let Some(v) = arg.strip_prefix("--batch=") {
+            return Some(v.to_string());
+        }
+    }
+    None
+}
+
+// 读取换行分隔的路径清单（忽略空行与 # 注释），相对路径按启动时的 cwd 解析
+fn load_batch_files(source: &str, cwd: &std::path::Path) -> Result<BatchFiles, String> {
+    let content = if source == "-" {
+        let mut buf = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut buf)
+            .map_err(|e| format!("从标准输入读取文件清单失败: {}", e))?;
+        buf
+    } else {
+        let list_path = cwd.join(source);
+        std::fs::read_to_string(&list_path)
+            .map_err(|e| format!("读取文件清单失败 {:?}: {}", list_path, e))?
+    };
+    let mut out = BatchFiles::default();
+    for line in content.lines() {
+        let line = line.trim().trim_matches('"');
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let path = cwd.join(line);
+        if path.is_file() {
+            let path = std::fs::canonicalize(&path).unwrap_or(path);
+            out.files
+                .push(strip_verbatim_prefix(&path.to_string_lossy()));
+        } else {
+            out.missing.push(line.to_string());
+        }
+    }
+    Ok(out)
+}
+
+// 处理启动参数中的批量清单：后台读取校验后授权其所在目录，暂存并 emit batch-files
+// （首次启动时前端可能尚未开始监听，可再用 take_batch_files 取走）
+// forwarded 为 true 表示参数来自二次启动的进程：其标准输入无法转交，--batch - 会误读当前实例的 stdin，直接拒绝
+fn dispatch_batch_files(app: &AppHandle, args: Vec<String>, cwd: PathBuf, forwarded: bool) {
+    let source = match batch_list_source(&args) {
+        Some(s) => s,
+        None => return,
+    };
+    if forwarded && source == "-" {
+        app_log!(
+            error,
+            "[app] 应用已在运行，无法通过标准输入转交批量清单（--batch -），请改用 --batch <清单文件>"
+        );
+        return;
+    }
+    let app = app.clone();
+    thread::spawn(move || match load_batch_files(&source, &cwd) {
+        Ok(batch) => {
+            app_log!(
+                info,
+                "[app] 批量清单 {}：{} 个文件，{} 个无效条目",
+                source,
+                batch.files.len(),
+                batch.missing.len()
+            );
+            let state = app.state::<AppState>();
+            for file in &batch.files {
+                if let Some(parent) = std::path::Path::new(file).parent() {
+                    state.authorize_dir(parent);
+                }
+            }
+            *state.pending_batch_files.lock().unwrap() = Some(batch.clone());
+            let _ = app.emit("batch-files", batch);
+        }
+        Err(e) => app_log!(error, "[app] {}", e),
+    });
+}
+
+// Tauri命令：取走尚未处理的命令行批量文件清单（取走后清空）
+#[tauri::command]
+async fn take_batch_files(state: State<'_, AppState>) -> Result<Option<BatchFiles>, String> {
+    Ok(state.pending_batch_files.lock().unwrap().take())
+}
+
+// 应用安装目录（传给后端的 SACV_INSTALL_DIR）：优先取主程序所在目录，失败时取资源目录的上级
+fn app_install_dir(resource_dir: &std::path::Path) -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|d| d.to_path_buf()))
+        .or_else(|| resource_dir.parent().map(|p| p.to_path_buf()))
+}
+
+// 比较两个安装目录是否相同：尽量规范化，Windows 下忽略大小写
+fn same_install_dir(a: &std::path::Path, b: &std::path::Path) -> bool {
+    let a = std::fs::canonicalize(a).unwrap_or_else(|_| a.to_path_buf());
+    let b = std::fs::canonicalize(b).unwrap_or_else(|_| b.to_path_buf());
+    if cfg!(target_os = "windows") {
+        a.to_string_lossy().to_lowercase() == b.to_string_lossy().to_lowercase()
+    } else {
+        a == b
+    }
+}
+
+// 同 identifier 的后端运行自其它安装目录（多份安装同时启动会互相抢端口）
+#[derive(Debug, Clone, Serialize)]
+struct InstallConflict {
+    port: u16,
+    version: Option<String>,
+    // 已运行后端自报的安装目录（/api/server/info 的 install_dir）
+    install_dir: String,
+    current_install_dir: String,
+}
+
+// 启动早期检测：已运行的本应用后端若来自另一安装目录则返回冲突信息；
+// 后端未上报 install_dir（旧版本）或同一目录时视为无冲突
+async fn detect_install_conflict(app_handle: &AppHandle) -> Option<InstallConflict> {
+    if std::env::var("SACV_NO_DISCOVER").ok().as_deref() == Some("1") {
+        return None;
+    }
+    let current = app_install_dir(&backend_resource_decision(app_handle).0)?;
+    let host = "127.0.0.1";
+    let (port, _) = discover_existing_backend_quick(host, false).await?;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(1000))
+        .build()
+        .ok()?;
+    let data = fetch_server_info_data(&client, host, port).await.ok()?;
+    let other = data
+        .get("install_dir")
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())?;
+    if same_install_dir(std::path::Path::new(other), &current) {
+        return None;
+    }
+    Some(InstallConflict {
+        port,
+        version: data
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        install_dir: other.to_string(),
+        current_install_dir: current.to_string_lossy().to_string(),
+    })
+}
+
+// Tauri命令：获取尚未处理的多份安装冲突（前端错过 install-conflict 事件时补取）
+#[tauri::command]
+async fn get_install_conflict(
+    state: State<'_, AppState>,
+) -> Result<Option<InstallConflict>, String> {
+    Ok(state.pending_install_conflict.lock().unwrap().clone())
+}
+
+// Tauri命令：处理多份安装冲突。reuse=true 复用另一安装的后端，否则忽略其端口并拉起本安装的后端
+#[tauri::command]
+async fn resolve_install_conflict(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    reuse: bool,
+) -> Result<BackendStatus, BackendError> {
+    let conflict = state
+        .pending_install_conflict
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| "没有待处理的多份安装冲突".to_string())?;
+    app_log!(
+        info,
+        "[app] 多份安装冲突：{}（port={}，install_dir={}）",
+        if reuse {
+            "复用已运行的后端"
+        } else {
+            "新建本安装的后端"
+        },
+        conflict.port,
+        conflict.install_dir
+    );
+    if !reuse {
+        state
+            .ignored_backend_ports
+            .lock()
+            .unwrap()
+            .push(conflict.port);
+    }
+    start_backend(state, app_handle, None).await
+}
+
 // 应用启动时的初始化
 fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    app_log!(
+        info,
+        "[app] 启动 version={} os={} arch={}",
+        app.package_info().version,
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
     let show_item = MenuItem::with_id(app, "tray_show", "显示主窗口", true, None::<&str>)?;
     let quit_item = MenuItem::with_id(app, "tray_quit", "退出", true, None::<&str>)?;
     let tray_menu = Menu::with_items(app, &[&show_item, &quit_item])?;
@@ -1367,12 +7527,7 @@ fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
                 ..
             } = event
             {
-                let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.unminimize();
-                    let _ = window.set_focus();
-                }
+                focus_main_window(tray.app_handle());
             }
         })
         .build(app)?;
@@ -1402,23 +7557,52 @@ fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
 
         window_builder.build()?;
     }
+    restore_window_state(app.handle());
 
     {
         let app_handle = app.handle().clone();
         tauri::async_runtime::spawn(async move {
+            // 另一安装目录的后端正在运行时先交由用户选择，由 resolve_install_conflict 继续启动
+            if let Some(conflict) = detect_install_conflict(&app_handle).await {
+                app_log!(
+                    warn,
+                    "[app] 检测到其它安装目录的后端在运行：{}（当前 {}，port={}）",
+                    conflict.install_dir,
+                    conflict.current_install_dir,
+                    conflict.port
+                );
+                *app_handle
+                    .state::<AppState>()
+                    .pending_install_conflict
+                    .lock()
+                    .unwrap() = Some(conflict.clone());
+                let _ = app_handle.emit("install-conflict", conflict);
+                return;
+            }
             let state = app_handle.state::<AppState>();
-            match start_backend(state, app_handle.clone()).await {
+            match start_backend(state, app_handle.clone(), None).await {
                 Ok(status) => {
                     if status.running && status.port != 0 {
-                        println!("[backend] 自动启动完成：http://127.0.0.1:{}", status.port);
+                        app_log!(
+                            info,
+                            "[backend] 自动启动完成：http://127.0.0.1:{}",
+                            status.port
+                        );
                     }
                 }
                 Err(e) => {
-                    eprintln!("[backend] 自动启动失败: {}", e);
+                    app_log!(error, "[backend] 自动启动失败: {}", e);
                 }
             }
         });
     }
+    spawn_idle_monitor(app.handle().clone());
+    dispatch_batch_files(
+        app.handle(),
+        std::env::args().collect(),
+        std::env::current_dir().unwrap_or_default(),
+        false,
+    );
 
     Ok(())
 }
@@ -1434,24 +7618,19 @@ fn main() {
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_process::init())
-        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.unminimize();
-                let _ = window.set_focus();
-            }
+        .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+            focus_main_window(app);
+            // 已有实例运行时，二次启动带的 --batch 转交给当前实例
+            dispatch_batch_files(app, argv, PathBuf::from(cwd), true);
         }))
         .manage(AppState::default())
         .setup(setup_app)
         .on_menu_event(|app, event| match event.id().as_ref() {
             "tray_show" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.unminimize();
-                    let _ = window.set_focus();
-                }
+                focus_main_window(app);
             }
             "tray_quit" => {
+                app_log!(info, "[window] 托盘菜单退出");
                 let state = app.state::<AppState>();
                 state.app_is_quitting.store(true, Ordering::SeqCst);
                 if let Some(window) = app.get_webview_window("main") {
@@ -1462,32 +7641,142 @@ fn main() {
             }
             _ => {}
         })
-        .on_window_event(|window, event| {
-            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::CloseRequested { api, .. } => {
                 let app = window.app_handle();
                 let state = app.state::<AppState>();
+                if !state.app_is_quitting.load(Ordering::SeqCst) && !close_to_tray_enabled(app) {
+                    app_log!(info, "[window] 关闭主窗口（未开启最小化到托盘），退出应用");
+                    state.app_is_quitting.store(true, Ordering::SeqCst);
+                }
                 if state.app_is_quitting.load(Ordering::SeqCst) {
+                    if state.quit_confirmed.load(Ordering::SeqCst) {
+                        return;
+                    }
+                    // 真正退出前确认后端没有运行中的任务；有任务时取消本次退出，交由前端弹确认框
+                    api.prevent_close();
+                    let app = app.clone();
+                    let window = window.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let state = app.state::<AppState>();
+                        let running = running_backend_task_count(&state).await;
+                        if running > 0 {
+                            app_log!(
+                                info,
+                                "[window] 退出被拦截：后端仍有 {} 个运行中任务",
+                                running
+                            );
+                            state.app_is_quitting.store(false, Ordering::SeqCst);
+                            let _ = window.show();
+                            let _ = window.set_focus();
+                            let _ = app.emit(
+                                "quit-confirm-requested",
+                                serde_json::json!({ "running_tasks": running }),
+                            );
+                        } else {
+                            state.quit_confirmed.store(true, Ordering::SeqCst);
+                            let _ = window.close();
+                        }
+                    });
                     return;
                 }
+                app_log!(info, "[window] 关闭主窗口，隐藏到托盘");
                 let _ = window.hide();
                 api.prevent_close();
             }
+            // 拖放文件：按扩展名分类后通过 files-dropped 一次性发给前端
+            tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                let dropped = classify_dropped_files(paths);
+                app_log!(
+                    info,
+                    "[window] 拖放 {} 个文件（视频 {}，其它 {}）",
+                    paths.len(),
+                    dropped.video.len(),
+                    dropped.others.len()
+                );
+                let _ = window.emit("files-dropped", dropped);
+            }
+            tauri::WindowEvent::Resized(_) | tauri::WindowEvent::Moved(_) => {
+                schedule_save_window_state(window);
+            }
+            _ => {}
         })
         .invoke_handler(tauri::generate_handler![
             start_backend,
+            probe_backend_launch,
+            preflight_check,
             stop_backend,
+            restart_backend,
+            set_backend_port,
+            set_backend_source,
+            force_reextract_backend,
+            kill_orphan_backends,
             get_backend_status,
+            get_backend_state,
+            cancel_reconnect,
+            ping_backend,
+            peek_backend,
+            get_backend_base_url,
+            get_server_info,
+            backend_request,
+            cancel_backend_request,
+            cancel_backend_task,
+            report_activity,
+            set_idle_policy,
+            set_backend_log_level,
+            set_app_language,
+            get_last_boot_trace,
+            get_backend_launch_env,
+            set_output_root,
+            get_output_root,
+            probe_video,
+            probe_videos,
+            extract_thumbnail,
+            detect_hw_encoders,
+            detect_gpu,
+            storage_usage,
+            list_background_tasks,
             select_video_file,
             select_output_directory,
+            read_text_file,
+            write_text_file,
+            move_file,
+            take_batch_files,
+            get_install_conflict,
+            resolve_install_conflict,
+            default_output_dir,
             get_app_info,
             show_notification,
+            set_notification_prefs,
             open_external_link,
+            copy_file_to_clipboard,
+            get_backend_log_path,
+            open_log_folder,
+            get_config_path,
+            open_config_folder,
+            get_app_dirs,
+            export_logs_bundle,
+            play_file,
+            reveal_in_file_manager,
             minimize_main_window,
             start_dragging_main_window,
             toggle_maximize_main_window,
             is_main_window_maximized,
-            close_main_window
+            close_main_window,
+            hide_to_tray,
+            show_from_tray,
+            set_close_to_tray,
+            set_add_to_recent,
+            confirm_app_quit,
+            restart_app,
+            export_config,
+            import_config
         ])
-        .run(tauri::generate_context!())
-        .expect("启动Tauri应用失败");
+        .build(tauri::generate_context!())
+        .expect("启动Tauri应用失败")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                cleanup_app(app);
+            }
+        });
 }